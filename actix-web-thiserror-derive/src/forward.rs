@@ -0,0 +1,53 @@
+/// Finds the field a variant forwards its response through under
+/// `#[response(auto_forward)]`, from its thiserror attributes: the field of an
+/// `#[error(transparent)]` variant, or its `#[from]`, `#[source]` or `source`
+/// field.
+pub fn get_auto_forward(variant: &syn::Variant) -> Option<(syn::Member, &syn::Field)> {
+  let fields = variant.fields.iter().enumerate().collect::<Vec<_>>();
+  let has_attr =
+    |field: &syn::Field, name: &str| field.attrs.iter().any(|attr| attr.path().is_ident(name));
+
+  let transparent = variant
+    .attrs
+    .iter()
+    .filter(|attr| attr.path().is_ident("error"))
+    .any(|attr| {
+      attr
+        .parse_args::<syn::Ident>()
+        .is_ok_and(|ident| ident == "transparent")
+    });
+
+  let field = match transparent {
+    true => fields.first(),
+    false => fields
+      .iter()
+      .find(|(_, field)| has_attr(field, "from"))
+      .or_else(|| fields.iter().find(|(_, field)| has_attr(field, "source")))
+      .or_else(|| {
+        fields
+          .iter()
+          .find(|(_, field)| field.ident.as_ref().is_some_and(|ident| ident == "source"))
+      }),
+  };
+
+  field.map(|(index, field)| (member(*index, field), *field))
+}
+
+/// Finds the field of a variant marked with `#[response(forward)]`: the one
+/// thiserror knows as its source, or its first field.
+pub fn get_forward(variant: &syn::Variant) -> Option<(syn::Member, &syn::Field)> {
+  get_auto_forward(variant).or_else(|| {
+    variant
+      .fields
+      .iter()
+      .next()
+      .map(|field| (member(0, field), field))
+  })
+}
+
+fn member(index: usize, field: &syn::Field) -> syn::Member {
+  match &field.ident {
+    Some(ident) => syn::Member::Named(ident.to_owned()),
+    None => syn::Member::Unnamed(syn::Index::from(index)),
+  }
+}
@@ -0,0 +1,152 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenTree;
+use quote::quote;
+use syn::DeriveInput;
+
+use crate::forward;
+use crate::response_error::get_container_options;
+
+pub fn derive_from_error_response(input: TokenStream) -> TokenStream {
+  let ast = syn::parse_macro_input!(input as DeriveInput);
+
+  let name = ast.ident;
+  let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+
+  let variants = if let syn::Data::Enum(syn::DataEnum { variants, .. }) = ast.data {
+    variants
+  } else {
+    return syn::Error::new_spanned(&name, "FromErrorResponse can only be derived for enums")
+      .to_compile_error()
+      .into();
+  };
+
+  let container_options = get_container_options(&ast.attrs);
+
+  let (forwards, constructors) = variants.iter().fold(
+    (vec![], vec![]),
+    |(mut forwards, mut constructors), variant| {
+      let ident = &variant.ident;
+      let ident_str = ident.to_string();
+      let ident_str = ident_str.trim_start_matches("r#");
+      let (explicit, details) = get_options(variant);
+      let auto =
+        !explicit && container_options.auto_forward && forward::get_auto_forward(variant).is_some();
+
+      match (&variant.fields, explicit || auto) {
+        (syn::Fields::Unnamed(fields), true) if fields.unnamed.len() == 1 => {
+          let ty = &fields.unnamed[0].ty;
+
+          // Fields found by `auto_forward` may not implement
+          // `FromErrorResponse`, in which case nothing is read through them.
+          forwards.push(match explicit {
+            true => quote! {
+              if let Some(inner) =
+                <#ty as ::actix_web_thiserror::FromErrorResponse>::from_parts(parts)
+              {
+                return Some(#name::#ident(inner));
+              }
+            },
+            false => quote! {
+              #[allow(unused_imports)]
+              use ::actix_web_thiserror::__private::{
+                ForwardFromParts as _, ForwardFromPartsFallback as _,
+              };
+
+              if let Some(inner) =
+                (&::actix_web_thiserror::__private::ForwardParts::<#ty>(::std::marker::PhantomData))
+                  .read_parts(parts)
+              {
+                return Some(#name::#ident(inner));
+              }
+            },
+          });
+        }
+
+        (syn::Fields::Unit, false) => {
+          constructors.push(quote! { #ident_str => Some(#name::#ident), });
+        }
+
+        (syn::Fields::Unnamed(fields), false)
+          if fields.unnamed.len() == 1 && details.as_deref() == Some("{0}") =>
+        {
+          constructors.push(quote! {
+            #ident_str => parts
+              .details
+              .to_owned()
+              .and_then(|details| serde_json::from_value(details).ok())
+              .map(#name::#ident),
+          });
+        }
+
+        _ => {}
+      }
+
+      (forwards, constructors)
+    },
+  );
+
+  let expanded = quote! {
+    impl #impl_generics ::actix_web_thiserror::FromErrorResponse for #name #ty_generics #where_clause {
+      fn from_parts(parts: &::actix_web_thiserror::ErrorResponseParts) -> Option<Self> {
+        let catalog = <Self as ::actix_web_thiserror::ThiserrorCatalog>::error_catalog();
+        let variant = parts
+          .matching_variants(catalog)
+          .find_map(|variant| match variant {
+            #(#constructors)*
+            _ => None,
+          });
+
+        if variant.is_some() {
+          return variant;
+        }
+
+        #(#forwards)*
+
+        None
+      }
+    }
+  };
+
+  TokenStream::from(expanded)
+}
+
+/// Finds whether a variant is marked with `forward`, and its `details` format
+/// string, in its #[response] attributes.
+fn get_options(variant: &syn::Variant) -> (bool, Option<String>) {
+  variant
+    .attrs
+    .iter()
+    .filter(|attr| attr.path().is_ident("response"))
+    .filter_map(|attr| match &attr.meta {
+      syn::Meta::List(meta_list) => Some(meta_list.tokens.to_owned()),
+      _ => None,
+    })
+    .fold((false, None), |(mut forward, mut details), tokens| {
+      let tokens = tokens.into_iter().collect::<Vec<_>>();
+
+      for (index, token) in tokens.iter().enumerate() {
+        let TokenTree::Ident(ident) = token else {
+          continue;
+        };
+        let assigned =
+          matches!(tokens.get(index + 1), Some(TokenTree::Punct(punct)) if punct.as_char() == '=');
+
+        match (ident.to_string().as_str(), assigned) {
+          ("forward", false) => forward = true,
+          ("details", true) => {
+            details = match tokens.get(index + 2) {
+              Some(TokenTree::Literal(literal)) => {
+                syn::parse_str::<syn::LitStr>(&literal.to_string())
+                  .ok()
+                  .map(|literal| literal.value())
+              }
+              _ => None,
+            }
+          }
+          _ => {}
+        }
+      }
+
+      (forward, details)
+    })
+}
@@ -1,8 +1,16 @@
 use proc_macro::TokenStream;
 
+mod forward;
+mod from_error_response;
 mod response_error;
+mod sensitive;
 
 #[proc_macro_derive(ResponseError, attributes(response))]
 pub fn derive_response_error(input: TokenStream) -> TokenStream {
   response_error::derive_response_error(input)
 }
+
+#[proc_macro_derive(FromErrorResponse, attributes(response))]
+pub fn derive_from_error_response(input: TokenStream) -> TokenStream {
+  from_error_response::derive_from_error_response(input)
+}
@@ -1,13 +1,19 @@
 use std::collections::{HashMap, HashSet};
 use std::iter::Peekable;
 
+use heck::{
+  ToKebabCase as _, ToLowerCamelCase as _, ToShoutyKebabCase as _, ToShoutySnakeCase as _,
+  ToSnakeCase as _, ToUpperCamelCase as _,
+};
 use proc_macro::TokenStream;
 use proc_macro2::{token_stream::IntoIter, TokenTree};
 use quote::quote;
 use quote::ToTokens as _;
-use syn::parse::Parser;
+use syn::punctuated::Punctuated;
 use syn::DeriveInput;
 
+use crate::{forward, sensitive};
+
 pub fn derive_response_error(input: TokenStream) -> TokenStream {
   let ast = syn::parse_macro_input!(input as DeriveInput);
 
@@ -21,13 +27,38 @@ pub fn derive_response_error(input: TokenStream) -> TokenStream {
     unimplemented!();
   };
 
+  let container_options = get_container_options(&ast.attrs);
+
+  let sensitive_fields = variants
+    .iter()
+    .map(|variant| (variant, sensitive::get_sensitive_fields(variant)))
+    .collect::<Vec<_>>();
+  let has_sensitive = sensitive_fields
+    .iter()
+    .any(|(_, sensitive)| sensitive.contains(&true));
+
+  let display = quote! {
+    ::actix_web_thiserror::ThiserrorResponse::redacted_display(self)
+      .unwrap_or_else(|| self.to_string())
+  };
+
   #[allow(clippy::type_complexity)]
-  let (forwards, mut status_map, mut reason_map, mut type_map, mut details_map): (
+  let (
+    explicit_forwards,
+    mut status_map,
+    mut reason_map,
+    mut type_map,
+    mut details_map,
+    mut public_map,
+    transform_map,
+  ): (
     HashSet<proc_macro2::Ident>,
     HashMap<proc_macro2::Ident, proc_macro2::TokenStream>,
     HashMap<proc_macro2::Ident, proc_macro2::TokenStream>,
     HashMap<proc_macro2::Ident, proc_macro2::TokenStream>,
     HashMap<proc_macro2::Ident, proc_macro2::TokenStream>,
+    HashMap<proc_macro2::Ident, proc_macro2::TokenStream>,
+    HashMap<proc_macro2::Ident, proc_macro2::TokenStream>,
   ) = variants.iter().fold(
     (
       HashSet::new(),
@@ -35,8 +66,19 @@ pub fn derive_response_error(input: TokenStream) -> TokenStream {
       HashMap::new(),
       HashMap::new(),
       HashMap::new(),
+      HashMap::new(),
+      HashMap::new(),
+    ),
+    |(
+      mut forwards,
+      mut status_map,
+      mut reason_map,
+      mut type_map,
+      mut details_map,
+      mut public_map,
+      mut transform_map,
     ),
-    |(mut forwards, mut status_map, mut reason_map, mut type_map, mut details_map), variant| {
+     variant| {
       let variant_ident = variant.ident.to_owned();
 
       for attr in variant.attrs.clone() {
@@ -56,6 +98,15 @@ pub fn derive_response_error(input: TokenStream) -> TokenStream {
                   let _ = forwards.insert(variant_ident.to_owned());
                 }
 
+                "expose" => {
+                  if public_map
+                    .insert(variant_ident.to_owned(), display.to_owned())
+                    .is_some()
+                  {
+                    panic!("`expose` and `public` are mutually exclusive in #[response]");
+                  }
+                }
+
                 _ => {
                   if tokens
                     .next()
@@ -103,6 +154,25 @@ pub fn derive_response_error(input: TokenStream) -> TokenStream {
                       }
                     }
 
+                    "public" => {
+                      let public = get_string(&mut tokens)
+                        .unwrap_or_else(|| panic!("Invalid `public` in #[response]"));
+
+                      if public_map
+                        .insert(variant_ident.to_owned(), quote! { #public.to_owned() })
+                        .is_some()
+                      {
+                        panic!("`expose` and `public` are mutually exclusive in #[response]");
+                      }
+                    }
+
+                    "transform" => {
+                      let transform = get_transform(&mut tokens)
+                        .unwrap_or_else(|| panic!("Invalid `transform` in #[response]"));
+
+                      transform_map.insert(variant_ident.to_owned(), transform);
+                    }
+
                     "details" => {
                       let details = get_details(&mut tokens);
 
@@ -133,10 +203,191 @@ pub fn derive_response_error(input: TokenStream) -> TokenStream {
         }
       }
 
-      (forwards, status_map, reason_map, type_map, details_map)
+      (
+        forwards,
+        status_map,
+        reason_map,
+        type_map,
+        details_map,
+        public_map,
+        transform_map,
+      )
     },
   );
 
+  let forward_fields = variants
+    .iter()
+    .filter_map(|variant| {
+      let explicit = explicit_forwards.contains(&variant.ident);
+
+      match explicit {
+        true => forward::get_forward(variant),
+        false if container_options.auto_forward => forward::get_auto_forward(variant),
+        false => None,
+      }
+      // Specialization cannot see through generic parameters, so generic fields
+      // found by `auto_forward` are forwarded as if explicitly, bounded on
+      // `ThiserrorResponse`.
+      .map(|(member, field)| {
+        let explicit = explicit || mentions_generics(&field.ty, &ast.generics);
+
+        (&variant.ident, member, &field.ty, explicit)
+      })
+    })
+    .collect::<Vec<_>>();
+  // Whether each variant forwards. Fields found by `auto_forward` may not
+  // implement `ThiserrorResponse`, in which case the variant is treated as any
+  // other, so this is only known once the type is checked.
+  let forwards = forward_fields
+    .iter()
+    .map(|(ident, _, ty, explicit)| {
+      let forwards = match explicit {
+        true => quote! { true },
+        false => forward_check(ty),
+      };
+
+      ((*ident).to_owned(), forwards)
+    })
+    .collect::<HashMap<_, _>>();
+  let mut guarded_reasons = HashMap::new();
+
+  if let Some(reason_case) = &container_options.reason_case {
+    let prefix = container_options
+      .reason_prefix
+      .as_deref()
+      .unwrap_or_default();
+
+    for variant in variants
+      .iter()
+      .filter(|variant| !explicit_forwards.contains(&variant.ident))
+    {
+      if !reason_map.contains_key(&variant.ident) {
+        if let Some(forwards) = forwards.get(&variant.ident) {
+          guarded_reasons.insert(variant.ident.to_owned(), forwards.to_owned());
+        }
+      }
+
+      reason_map
+        .entry(variant.ident.to_owned())
+        .or_insert_with(|| {
+          let variant_str = variant.ident.to_string();
+          let reason = format!(
+            "{}{}",
+            prefix,
+            reason_case.apply(variant_str.trim_start_matches("r#")),
+          );
+
+          syn::LitStr::new(&reason, variant.ident.span()).into_token_stream()
+        });
+    }
+  } else if container_options.reason_prefix.is_some() {
+    panic!("`reason_prefix` in #[response] requires `reason_from_variant`");
+  }
+
+  if container_options.unique_reasons {
+    if let Err(err) = check_unique_reasons(&variants, &reason_map) {
+      return err.to_compile_error().into();
+    }
+  }
+
+  // The transforms variants are rendered with, other than the global one.
+  // `custom` needs an instance of the error, so it is left out.
+  let variant_transforms = variants
+    .iter()
+    .filter_map(|variant| {
+      transform_map
+        .get(&variant.ident)
+        .or(container_options.transform.as_ref())
+        .filter(|transform| transform.to_string() != "self")
+        .map(|transform| (variant.ident.to_owned(), transform.to_owned()))
+    })
+    .collect::<Vec<_>>();
+
+  let catalog = get_catalog(
+    &name_str,
+    &variants,
+    &forwards,
+    &guarded_reasons,
+    &status_map,
+    &reason_map,
+    &type_map,
+    &details_map,
+    &variant_transforms,
+  );
+
+  let global_variants = variants
+    .iter()
+    .filter(|variant| {
+      !variant_transforms
+        .iter()
+        .any(|(ident, _)| ident == &variant.ident)
+    })
+    .map(|variant| {
+      variant
+        .ident
+        .to_string()
+        .trim_start_matches("r#")
+        .to_owned()
+    });
+  let (transform_variants, transform_refs): (Vec<_>, Vec<_>) = variant_transforms
+    .iter()
+    .map(|(ident, transform)| {
+      (
+        ident.to_string().trim_start_matches("r#").to_owned(),
+        transform,
+      )
+    })
+    .unzip();
+
+  let mut response_generics = ast.generics.clone();
+  let mut response_bounded: Vec<&syn::Type> = vec![];
+
+  for (_, _, ty, explicit) in &forward_fields {
+    if *explicit && mentions_generics(ty, &ast.generics) && !response_bounded.contains(ty) {
+      response_bounded.push(ty);
+      response_generics
+        .make_where_clause()
+        .predicates
+        .push(syn::parse_quote! { #ty: ::actix_web_thiserror::ThiserrorResponse });
+    }
+  }
+
+  let (response_impl_generics, response_ty_generics, response_where_clause) =
+    response_generics.split_for_impl();
+
+  let mut openapi_generics = ast.generics.clone();
+  let mut forward_types: Vec<&syn::Type> = vec![];
+  let mut auto_forward_types: Vec<&syn::Type> = vec![];
+
+  // Forwarded types may not implement `IntoResponses`, as with hand-written
+  // `ThiserrorResponse` implementations or fields found by `auto_forward`, so
+  // they are merged through `ForwardResponses` rather than bounded on. Only
+  // forwarded generic types are bounded on, where that would pick the
+  // fallback.
+  for (_, _, ty, explicit) in &forward_fields {
+    if *explicit && mentions_generics(ty, &ast.generics) && !forward_types.contains(ty) {
+      forward_types.push(ty);
+    }
+  }
+
+  for (_, _, ty, _) in &forward_fields {
+    if !forward_types.contains(ty) && !auto_forward_types.contains(ty) {
+      auto_forward_types.push(ty);
+    }
+  }
+
+  for forward_type in &forward_types {
+    openapi_generics
+      .make_where_clause()
+      .predicates
+      .push(syn::parse_quote! {
+        #forward_type: ::actix_web_thiserror::__private::utoipa::IntoResponses
+      });
+  }
+
+  let (openapi_impl_generics, openapi_ty_generics, openapi_where_clause) =
+    openapi_generics.split_for_impl();
+
   let status_code_match = match status_map.len() {
     0 => None,
     _ => {
@@ -158,10 +409,13 @@ pub fn derive_response_error(input: TokenStream) -> TokenStream {
     _ => {
       let mut body: Vec<proc_macro2::TokenStream> = reason_map
         .drain()
-        .map(|(ident, reason)| {
-          quote! {
-            #name::#ident { .. } => Some(serde_json::to_value(#reason).ok()),
-          }
+        .map(|(ident, reason)| match guarded_reasons.get(&ident) {
+          Some(forwards) => quote! {
+            #name::#ident { .. } if !#forwards => serde_json::to_value(#reason).ok().into(),
+          },
+          None => quote! {
+            #name::#ident { .. } => serde_json::to_value(#reason).ok().into(),
+          },
         })
         .collect();
 
@@ -176,7 +430,7 @@ pub fn derive_response_error(input: TokenStream) -> TokenStream {
         .drain()
         .map(|(ident, _type)| {
           quote! {
-            #name::#ident { .. } => Some(Some(#_type.to_owned())),
+            #name::#ident { .. } => ::actix_web_thiserror::MetaValue::Set(#_type.to_owned()),
           }
         })
         .collect();
@@ -200,8 +454,39 @@ pub fn derive_response_error(input: TokenStream) -> TokenStream {
             })
             .expect("Failed to find details");
 
+          let details_sensitive = sensitive_fields
+            .iter()
+            .find(|(variant, _)| variant.ident == ident)
+            .and_then(|(_, sensitive)| sensitive.first().copied())
+            .unwrap_or(false);
+
+          match details_sensitive {
+            true => {
+              let redacted = sensitive::REDACTED;
+
+              quote! {
+                #name::#ident(..) => serde_json::to_value(#redacted).ok().into(),
+              }
+            }
+            false => quote! {
+              #name::#ident(details) => serde_json::to_value(#details).ok().into(),
+            },
+          }
+        })
+        .collect();
+
+      Some(proc_macro2::TokenStream::from_iter(body.drain(..)))
+    }
+  };
+
+  let public_match = match public_map.len() {
+    0 => None,
+    _ => {
+      let mut body: Vec<proc_macro2::TokenStream> = public_map
+        .drain()
+        .map(|(ident, public)| {
           quote! {
-            #name::#ident(details) => Some(serde_json::to_value(#details).ok()),
+            #name::#ident { .. } => ::actix_web_thiserror::MetaValue::Set(#public),
           }
         })
         .collect();
@@ -210,157 +495,642 @@ pub fn derive_response_error(input: TokenStream) -> TokenStream {
     }
   };
 
-  let (status_code_forwards, reason_forwards, type_forwards, details_forwards) =
-    match forwards.len() {
-      0 => (None, None, None, None),
-      _ => {
-        let mut streams = vec![
-          quote! { status_code },
-          quote! { reason },
-          quote! { _type },
-          quote! { details },
-        ]
-        .drain(..)
-        .map(|func| {
-          proc_macro2::TokenStream::from_iter(forwards.iter().map(|variant| {
-            quote! {
-              #name::#variant(inner) => ::actix_web_thiserror::ThiserrorResponse::#func(inner),
-            }
-          }))
+  let meta_forwards = forward_fields
+    .iter()
+    .map(|(variant, member, _, explicit)| match explicit {
+      true => quote! {
+        #name::#variant { #member: inner, .. } =>
+          ::actix_web_thiserror::ThiserrorResponse::response_meta(inner),
+      },
+      // Fields that do not implement `ThiserrorResponse` leave everything
+      // unset.
+      false => quote! {
+        #name::#variant { #member: inner, .. } => {
+          #[allow(unused_imports)]
+          use ::actix_web_thiserror::__private::{ForwardFallback as _, ForwardResponse as _};
+
+          (&::actix_web_thiserror::__private::Forward(inner)).response_meta()
+        }
+      },
+    })
+    .collect::<Vec<_>>();
+
+  let (default_transform, default_status_code) = match container_options.transform {
+    Some(transform) => (
+      quote! { ::actix_web_thiserror::ResponseTransform::transform_with_context(#transform, &context) },
+      quote! { ::actix_web_thiserror::ResponseTransform::default_error_status_code(#transform) },
+    ),
+    None => (
+      quote! { ::actix_web_thiserror::apply_global_transform_with_context(&context) },
+      quote! { ::actix_web_thiserror::default_global_error_status_code() },
+    ),
+  };
+  let (transform_match, status_code_defaults): (Vec<_>, Vec<_>) = transform_map
+    .iter()
+    .map(|(ident, transform)| {
+      (
+        quote! {
+          #name::#ident { .. } =>
+            ::actix_web_thiserror::ResponseTransform::transform_with_context(#transform, &context),
+        },
+        quote! {
+          #name::#ident { .. } =>
+            ::actix_web_thiserror::ResponseTransform::default_error_status_code(#transform),
+        },
+      )
+    })
+    .unzip();
+
+  let variant_match = variants
+    .iter()
+    .map(|variant| {
+      let ident = &variant.ident;
+      let ident_str = ident.to_string();
+      let ident_str = ident_str.trim_start_matches("r#");
+
+      quote! { #name::#ident { .. } => #ident_str, }
+    })
+    .collect::<Vec<_>>();
+
+  // Generic enums cannot be downcast to without knowing their parameters.
+  let registration = match ast.generics.params.is_empty() {
+    true => Some(quote! {
+      ::actix_web_thiserror::__submit_registered_error!(#name, #name_str, |err: &#name| match err {
+        #(#variant_match)*
+        #[allow(unreachable_patterns)]
+        _ => "",
+      });
+    }),
+    false => None,
+  };
+
+  // Sensitive fields are masked in logs, including those of forwarded errors.
+  let redacts = has_sensitive || !forward_fields.is_empty();
+  let (redacted, log, redacted_display) = match redacts {
+    true => {
+      let forwarded = forward_fields
+        .iter()
+        .map(|(variant, member, _, explicit)| {
+          (
+            *variant,
+            sensitive::Forwarded {
+              member,
+              explicit: *explicit,
+            },
+          )
+        })
+        .collect::<Vec<_>>();
+      let forwarded = |variant: &syn::Variant| {
+        forwarded
+          .iter()
+          .find(|(ident, _)| *ident == &variant.ident)
+          .map(|(_, forwarded)| forwarded)
+      };
+      let debug_arms = sensitive_fields
+        .iter()
+        .filter_map(|(variant, sensitive)| {
+          sensitive::get_redacted_debug_arm(&name, variant, sensitive, forwarded(variant))
+        })
+        .collect::<Vec<_>>();
+      let display_arms = sensitive_fields
+        .iter()
+        .filter_map(|(variant, sensitive)| {
+          sensitive::get_redacted_display_arm(&name, variant, sensitive, forwarded(variant))
         })
         .collect::<Vec<_>>();
 
-        (
-          Some(streams.remove(0)),
-          Some(streams.remove(0)),
-          Some(streams.remove(0)),
-          Some(streams.remove(0)),
-        )
-      }
-    };
+      (
+        quote! {
+          #[allow(unreachable_patterns)]
+          fn redacted_display(&self) -> Option<String> {
+            match self {
+              #(#display_arms)*
+              _ => None,
+            }
+          }
 
-  let transform = ast
-    .attrs
-    .into_iter()
-    .find_map(|x| {
-      if !(x.path().segments.len() == 1 && x.path().segments.first()?.ident == "response") {
-        return None;
-      }
-      let proc_macro2::TokenTree::Group(group) = x.into_token_stream().into_iter().next()? else {
-        return None;
-      };
-      let ident_assigns =
-        syn::punctuated::Punctuated::<syn::ExprAssign, syn::Token![,]>::parse_terminated
-          .parse(group.stream().into())
-          .unwrap()
-          .into_iter()
-          .filter_map(|x| {
-            let syn::Expr::Path(syn::ExprPath { path, .. }) = *x.left else {
-              return None;
-            };
-            let left = path.get_ident()?;
+          #[allow(unreachable_patterns)]
+          fn redacted_debug(&self) -> Option<String> {
+            match self {
+              #(#debug_arms)*
+              _ => None,
+            }
+          }
+        },
+        quote! {
+          let redacted_debug = ::actix_web_thiserror::ThiserrorResponse::redacted_debug(self);
+
+          log::error!(
+            "Response error [{correlation_id}]: {err}\n\t{name}({debug:?})",
+            correlation_id = context.correlation_id,
+            name = #name_str,
+            err = context.redacted_display.as_deref().map_or_else(|| self.to_string(), str::to_owned),
+            debug = ::actix_web_thiserror::__private::DebugFn(&|f| match &redacted_debug {
+              Some(debug) => f.write_str(debug),
+              None => ::std::fmt::Debug::fmt(self, f),
+            }),
+          );
+        },
+        quote! {
+          context.redacted_display = ::actix_web_thiserror::ThiserrorResponse::redacted_display(self);
+        },
+      )
+    }
+    false => (
+      quote! {},
+      quote! {
+        log::error!(
+          "Response error [{correlation_id}]: {err}\n\t{name}({err:?})",
+          correlation_id = context.correlation_id,
+          name = #name_str,
+          err = &self,
+        );
+      },
+      quote! {},
+    ),
+  };
 
-            let syn::Expr::Path(syn::ExprPath { path, .. }) = *x.right else {
-              return None;
-            };
-            let right = path.get_ident()?;
+  let expanded = quote! {
+    const _: () = {
+      impl #response_impl_generics ::actix_web_thiserror::ThiserrorResponse
+        for #name #response_ty_generics #response_where_clause
+      {
+        fn response_meta(&self) -> ::actix_web_thiserror::ResponseMeta {
+          let mut meta = ::actix_web_thiserror::ResponseMeta::new();
 
-            Some((left.to_string(), right.to_string()))
+          meta.status_code = match self {
+            #status_code_match
+            _ => None,
+          };
+          meta.reason = match self {
+            #reason_match
+            _ => ::actix_web_thiserror::MetaValue::Unset,
+          };
+          meta._type = match self {
+            #type_match
+            _ => ::actix_web_thiserror::MetaValue::Unset,
+          };
+          meta.details = match self {
+            #details_match
+            _ => ::actix_web_thiserror::MetaValue::Unset,
+          };
+          meta.public_message = match self {
+            #public_match
+            _ => ::actix_web_thiserror::MetaValue::Unset,
+          };
+
+          meta.or(match self {
+            #(#meta_forwards)*
+            #[allow(unreachable_patterns)]
+            _ => ::actix_web_thiserror::ResponseMeta::new(),
           })
-          .collect::<HashMap<String, String>>();
+        }
 
-      if ident_assigns.get("transform")? == "custom" {
-        return Some(quote! { self.transform });
+        #redacted
       }
 
-      // could add additional to check for invalid options?
+      fn error_catalog() -> &'static ::actix_web_thiserror::ErrorCatalog {
+        static CATALOG: ::std::sync::OnceLock<::actix_web_thiserror::ErrorCatalog> =
+          ::std::sync::OnceLock::new();
 
-      None
-    })
-    .unwrap_or(quote! { actix_web_thiserror::apply_global_transform });
+        CATALOG.get_or_init(|| #catalog)
+      }
 
-  let expanded = quote! {
-    impl #impl_generics ::actix_web_thiserror::ThiserrorResponse for #name #ty_generics #where_clause {
-      fn status_code(&self) -> Option<actix_web::http::StatusCode> {
-        match self {
-          #status_code_match
-          _ => None,
+      ::actix_web_thiserror::__submit_error_catalog!(error_catalog);
+
+      #registration
+
+      impl #impl_generics ::actix_web_thiserror::ThiserrorCatalog for #name #ty_generics #where_clause {
+        fn error_catalog() -> &'static ::actix_web_thiserror::ErrorCatalog {
+          error_catalog()
         }
       }
 
-      fn reason(&self) -> Option<Option<serde_json::Value>> {
-        match self {
-          #reason_match
-          _ => None,
+      ::actix_web_thiserror::__cfg_openapi! {
+        impl #openapi_impl_generics ::actix_web_thiserror::__private::utoipa::IntoResponses
+          for #name #openapi_ty_generics #openapi_where_clause
+        {
+          fn responses() -> ::std::collections::BTreeMap<
+            ::std::string::String,
+            ::actix_web_thiserror::__private::utoipa::openapi::RefOr<
+              ::actix_web_thiserror::__private::utoipa::openapi::Response,
+            >,
+          > {
+            let mut responses = ::actix_web_thiserror::openapi::responses(
+              &::actix_web_thiserror::openapi::select(error_catalog(), &[#(#global_variants),*]),
+            );
+
+            #(
+              ::actix_web_thiserror::openapi::merge_responses(
+                &mut responses,
+                ::actix_web_thiserror::openapi::responses_with(
+                  #transform_refs,
+                  &::actix_web_thiserror::openapi::select(error_catalog(), &[#transform_variants]),
+                ),
+              );
+            )*
+
+            #(
+              ::actix_web_thiserror::openapi::merge_responses(
+                &mut responses,
+                <#forward_types as ::actix_web_thiserror::__private::utoipa::IntoResponses>::responses(),
+              );
+            )*
+
+            #({
+              #[allow(unused_imports)]
+              use ::actix_web_thiserror::__private::{
+                ForwardIntoResponses as _, ForwardIntoResponsesFallback as _,
+              };
+
+              ::actix_web_thiserror::openapi::merge_responses(
+                &mut responses,
+                (&::actix_web_thiserror::__private::ForwardResponses::<#auto_forward_types>(
+                  ::std::marker::PhantomData,
+                ))
+                  .responses(),
+              );
+            })*
+
+            responses
+          }
         }
       }
 
-      fn _type(&self) -> Option<Option<String>> {
-        match self {
-          #type_match
-          _ => None,
+      impl #response_impl_generics actix_web::error::ResponseError
+        for #name #response_ty_generics #response_where_clause
+      {
+        fn status_code(&self) -> actix_web::http::StatusCode {
+          ::actix_web_thiserror::ThiserrorResponse::response_meta(self)
+            .status_code
+            .unwrap_or_else(|| match self {
+              #(#status_code_defaults)*
+              #[allow(unreachable_patterns)]
+              _ => #default_status_code,
+            })
         }
-      }
 
-      fn details(&self) -> Option<Option<serde_json::Value>> {
-        match self {
-          #details_match
-          _ => None,
+        fn error_response(&self) -> actix_web::HttpResponse {
+          let meta = ::actix_web_thiserror::ThiserrorResponse::response_meta(self);
+
+          let mut context = ::actix_web_thiserror::ErrorContext::new(
+            #name_str,
+            self,
+            actix_web::error::ResponseError::status_code(self),
+          );
+          context.variant = match self {
+            #(#variant_match)*
+            #[allow(unreachable_patterns)]
+            _ => "",
+          };
+          context.reason = meta.reason.into_option();
+          context._type = meta._type.into_option();
+          context.details = meta.details.into_option();
+          context.public_message = meta.public_message.into_option();
+          #redacted_display
+
+          #log
+
+          let response = ::actix_web_thiserror::__private::mark_rendered(match self {
+            #(#transform_match)*
+            #[allow(unreachable_patterns)]
+            _ => #default_transform,
+          });
+
+          ::actix_web_thiserror::observe_error(&context);
+
+          response
         }
       }
+    };
+  };
+
+  TokenStream::from(expanded)
+}
+
+/// Builds the expression for the `ErrorCatalog` describing every variant.
+#[allow(clippy::too_many_arguments)]
+fn get_catalog(
+  name_str: &str,
+  variants: &Punctuated<syn::Variant, syn::Token![,]>,
+  forwards: &HashMap<proc_macro2::Ident, proc_macro2::TokenStream>,
+  guarded_reasons: &HashMap<proc_macro2::Ident, proc_macro2::TokenStream>,
+  status_map: &HashMap<proc_macro2::Ident, proc_macro2::TokenStream>,
+  reason_map: &HashMap<proc_macro2::Ident, proc_macro2::TokenStream>,
+  type_map: &HashMap<proc_macro2::Ident, proc_macro2::TokenStream>,
+  details_map: &HashMap<proc_macro2::Ident, proc_macro2::TokenStream>,
+  variant_transforms: &[(proc_macro2::Ident, proc_macro2::TokenStream)],
+) -> proc_macro2::TokenStream {
+  fn option(value: Option<proc_macro2::TokenStream>) -> proc_macro2::TokenStream {
+    value
+      .map(|value| quote! { Some(#value) })
+      .unwrap_or(quote! { None })
+  }
+
+  let entries = variants.iter().map(|variant| {
+    let ident = &variant.ident;
+    let variant_str = ident.to_string();
+    let variant_str = variant_str.trim_start_matches("r#");
+
+    let status = option(
+      status_map
+        .get(ident)
+        .map(|status_code| quote! { (#status_code).as_u16() }),
+    );
+    let reason = reason_map
+      .get(ident)
+      .map(|reason| match guarded_reasons.get(ident) {
+        Some(forwards) => quote! {
+          match #forwards {
+            true => None,
+            false => serde_json::to_value(#reason).ok(),
+          }
+        },
+        None => quote! { serde_json::to_value(#reason).ok() },
+      })
+      .unwrap_or(quote! { None });
+    let _type = option(type_map.get(ident).cloned());
+    let display = option(get_display(variant).map(|display| display.into_token_stream()));
+    let details = option(details_map.get(ident).cloned());
+    let details_type = option(
+      details_map
+        .get(ident)
+        .filter(|details| details.to_string() == "\"{0}\"")
+        .and_then(|_| variant.fields.iter().next())
+        .map(|field| {
+          let ty = field.ty.to_token_stream().to_string();
+
+          quote! { #ty }
+        }),
+    );
+    let forward = forwards.get(ident).cloned().unwrap_or(quote! { false });
+    let transform_status = option(
+      variant_transforms
+        .iter()
+        .find(|(transform_ident, _)| transform_ident == ident)
+        .map(|(_, transform)| {
+          quote! {
+            ::actix_web_thiserror::ResponseTransform::default_error_status_code(#transform).as_u16()
+          }
+        }),
+    );
+
+    quote! {
+      ::actix_web_thiserror::ErrorCatalogEntry {
+        variant: #variant_str,
+        status: #status,
+        reason: #reason,
+        _type: #_type,
+        display: #display,
+        details: #details,
+        details_type: #details_type,
+        forward: #forward,
+        transform_status: #transform_status,
+      }
     }
+  });
 
-    impl #impl_generics actix_web::error::ResponseError for #name #ty_generics #where_clause {
-      fn status_code(&self) -> actix_web::http::StatusCode {
-        match ::actix_web_thiserror::ThiserrorResponse::status_code(self) {
-          Some(status_code) => status_code,
-          _ => {
-            match self {
-              #status_code_forwards
-              _ => None
+  quote! {
+    ::actix_web_thiserror::ErrorCatalog {
+      name: #name_str,
+      entries: vec![#(#entries),*],
+    }
+  }
+}
+
+/// Finds the format string of thiserror's `#[error("...")]` on a variant.
+fn get_display(variant: &syn::Variant) -> Option<syn::LitStr> {
+  variant
+    .attrs
+    .iter()
+    .filter(|attr| attr.path().is_ident("error"))
+    .find_map(|attr| {
+      attr
+        .parse_args_with(|input: syn::parse::ParseStream| {
+          let display = input.parse::<syn::LitStr>()?;
+          input.parse::<proc_macro2::TokenStream>()?;
+
+          Ok(display)
+        })
+        .ok()
+    })
+}
+
+/// Whether `ty` refers to any of the type parameters in `generics`.
+fn mentions_generics(ty: &syn::Type, generics: &syn::Generics) -> bool {
+  fn mentions(tokens: proc_macro2::TokenStream, params: &[&syn::Ident]) -> bool {
+    tokens.into_iter().any(|token| match token {
+      TokenTree::Ident(ident) => params.contains(&&ident),
+      TokenTree::Group(group) => mentions(group.stream(), params),
+      _ => false,
+    })
+  }
+
+  let params = generics
+    .type_params()
+    .map(|param| &param.ident)
+    .collect::<Vec<_>>();
+
+  !params.is_empty() && mentions(ty.to_token_stream(), &params)
+}
+
+/// Builds the expression checking whether a field found by `auto_forward`
+/// implements `ThiserrorResponse`.
+fn forward_check(ty: &syn::Type) -> proc_macro2::TokenStream {
+  quote! {{
+    #[allow(unused_imports)]
+    use ::actix_web_thiserror::__private::{ForwardTypeFallback as _, ForwardTypeResponse as _};
+
+    (&::actix_web_thiserror::__private::ForwardType::<#ty>(::std::marker::PhantomData)).forwards()
+  }}
+}
+
+#[derive(Default)]
+pub(crate) struct ContainerOptions {
+  transform: Option<proc_macro2::TokenStream>,
+  pub(crate) auto_forward: bool,
+  reason_case: Option<ReasonCase>,
+  reason_prefix: Option<String>,
+  unique_reasons: bool,
+}
+
+pub(crate) fn get_container_options(attrs: &[syn::Attribute]) -> ContainerOptions {
+  attrs
+    .iter()
+    .filter(|attr| attr.path().is_ident("response"))
+    .fold(ContainerOptions::default(), |mut options, attr| {
+      let metas = attr
+        .parse_args_with(Punctuated::<syn::Meta, syn::Token![,]>::parse_terminated)
+        .unwrap_or_else(|err| panic!("Invalid #[response] options: {}", err));
+
+      for meta in metas {
+        let ident = meta
+          .path()
+          .get_ident()
+          .map(|ident| ident.to_string())
+          .unwrap_or_else(|| meta.path().to_token_stream().to_string());
+
+        match &ident as &str {
+          "transform" => {
+            let value = match meta {
+              syn::Meta::NameValue(syn::MetaNameValue { value, .. }) => value,
+              _ => panic!("Invalid `transform` in #[response]"),
+            };
+
+            options.transform = Some(transform_reference(value));
+          }
+
+          "reason_from_variant" => {
+            let case = get_container_string(meta)
+              .unwrap_or_else(|| panic!("Invalid `reason_from_variant` in #[response]"));
+
+            options.reason_case = Some(ReasonCase::from_name(&case).unwrap_or_else(|| {
+              panic!(
+                "Invalid `reason_from_variant` in #[response]: unknown case {:?}",
+                &case
+              )
+            }));
+          }
+
+          "reason_prefix" => {
+            options.reason_prefix = Some(
+              get_container_string(meta)
+                .unwrap_or_else(|| panic!("Invalid `reason_prefix` in #[response]")),
+            );
+          }
+
+          "auto_forward" => {
+            if !matches!(meta, syn::Meta::Path(_)) {
+              panic!("Invalid `auto_forward` in #[response]");
             }
-              .unwrap_or(actix_web_thiserror::default_global_error_status_code())
-          },
-        }
-      }
 
-      fn error_response(&self) -> actix_web::HttpResponse {
-        let reason: Option<serde_json::Value> = ::actix_web_thiserror::ThiserrorResponse::reason(self)
-          .unwrap_or(match self {
-            #reason_forwards
-            _ => None,
+            options.auto_forward = true;
           }
-            .and_then(|value| value));
 
-        let _type: Option<String> = ::actix_web_thiserror::ThiserrorResponse::_type(self)
-          .unwrap_or(match self {
-            #type_forwards
-            _ => None,
+          "unique_reasons" => {
+            if !matches!(meta, syn::Meta::Path(_)) {
+              panic!("Invalid `unique_reasons` in #[response]");
+            }
+
+            options.unique_reasons = true;
           }
-            .and_then(|value| value));
 
-        let details: Option<serde_json::Value> = ::actix_web_thiserror::ThiserrorResponse::details(self)
-          .unwrap_or(match self {
-            #details_forwards
-            _ => None,
+          _ => {
+            panic!("Unknown #[response] option: {}", &ident);
           }
-            .and_then(|value| value));
-
-        log::error!("Response error: {err}\n\t{name}({err:?})", name = #name_str, err = &self);
-
-        #transform(
-          #name_str,
-          &self,
-          self.status_code(),
-          reason,
-          _type,
-          details,
-        )
+        }
+      }
+
+      options
+    })
+}
+
+fn get_container_string(meta: syn::Meta) -> Option<String> {
+  match meta {
+    syn::Meta::NameValue(syn::MetaNameValue {
+      value: syn::Expr::Lit(syn::ExprLit {
+        lit: syn::Lit::Str(value),
+        ..
+      }),
+      ..
+    }) => Some(value.value()),
+    _ => None,
+  }
+}
+
+/// Rejects literal reasons that are shared by more than one variant, pointing
+/// at every variant involved.
+fn check_unique_reasons(
+  variants: &Punctuated<syn::Variant, syn::Token![,]>,
+  reason_map: &HashMap<proc_macro2::Ident, proc_macro2::TokenStream>,
+) -> syn::Result<()> {
+  let mut seen: HashMap<String, &syn::Variant> = HashMap::new();
+  let mut errors: Option<syn::Error> = None;
+
+  for variant in variants {
+    let Some(reason) = reason_map
+      .get(&variant.ident)
+      .and_then(|reason| syn::parse2::<syn::LitStr>(reason.to_owned()).ok())
+    else {
+      continue;
+    };
+
+    match seen.get(&reason.value()) {
+      Some(first) => {
+        let mut error = syn::Error::new_spanned(
+          &variant.ident,
+          format!(
+            "duplicate reason {:?} in #[response(unique_reasons)]",
+            reason.value()
+          ),
+        );
+        error.combine(syn::Error::new_spanned(
+          &first.ident,
+          format!(
+            "reason {:?} first used by `{}`",
+            reason.value(),
+            first.ident
+          ),
+        ));
+
+        match errors.as_mut() {
+          Some(errors) => errors.combine(error),
+          None => errors = Some(error),
+        }
+      }
+      None => {
+        seen.insert(reason.value(), variant);
       }
     }
-  };
+  }
 
-  TokenStream::from(expanded)
+  match errors {
+    Some(errors) => Err(errors),
+    None => Ok(()),
+  }
+}
+
+/// The casing applied to variant names by `#[response(reason_from_variant = "...")]`.
+///
+/// The names follow the ones accepted by serde's `rename_all`.
+enum ReasonCase {
+  Lower,
+  Upper,
+  Pascal,
+  Camel,
+  Snake,
+  ScreamingSnake,
+  Kebab,
+  ScreamingKebab,
+}
+
+impl ReasonCase {
+  fn from_name(name: &str) -> Option<Self> {
+    match name {
+      "lowercase" => Some(Self::Lower),
+      "UPPERCASE" => Some(Self::Upper),
+      "PascalCase" => Some(Self::Pascal),
+      "camelCase" => Some(Self::Camel),
+      "snake_case" => Some(Self::Snake),
+      "SCREAMING_SNAKE_CASE" => Some(Self::ScreamingSnake),
+      "kebab-case" => Some(Self::Kebab),
+      "SCREAMING-KEBAB-CASE" => Some(Self::ScreamingKebab),
+      _ => None,
+    }
+  }
+
+  fn apply(&self, variant: &str) -> String {
+    match self {
+      Self::Lower => variant.to_lowercase(),
+      Self::Upper => variant.to_uppercase(),
+      Self::Pascal => variant.to_upper_camel_case(),
+      Self::Camel => variant.to_lower_camel_case(),
+      Self::Snake => variant.to_snake_case(),
+      Self::ScreamingSnake => variant.to_shouty_snake_case(),
+      Self::Kebab => variant.to_kebab_case(),
+      Self::ScreamingKebab => variant.to_shouty_kebab_case(),
+    }
+  }
 }
 
 fn get_ident_stream(tokens: &mut Peekable<IntoIter>) -> Option<proc_macro2::TokenStream> {
@@ -376,14 +1146,10 @@ fn get_ident_stream(tokens: &mut Peekable<IntoIter>) -> Option<proc_macro2::Toke
         ident_fragments.push(tokens.next().expect("should exist"));
 
         if !match tokens.next() {
-          Some(TokenTree::Punct(punct)) => {
-            if punct.as_char() == ':' {
-              ident_fragments.push(TokenTree::Punct(punct));
+          Some(TokenTree::Punct(punct)) if punct.as_char() == ':' => {
+            ident_fragments.push(TokenTree::Punct(punct));
 
-              true
-            } else {
-              false
-            }
+            true
           }
           _ => false,
         } {
@@ -479,6 +1245,29 @@ fn get_type(tokens: &mut Peekable<IntoIter>) -> Option<proc_macro2::TokenStream>
   }
 }
 
+/// Reads the expression of a `transform` option, up to the next comma.
+fn get_transform(tokens: &mut Peekable<IntoIter>) -> Option<proc_macro2::TokenStream> {
+  let mut expr = proc_macro2::TokenStream::new();
+
+  while let Some(token) =
+    tokens.next_if(|token| !matches!(token, TokenTree::Punct(punct) if punct.as_char() == ','))
+  {
+    expr.extend([token]);
+  }
+
+  syn::parse2::<syn::Expr>(expr).ok().map(transform_reference)
+}
+
+/// A reference to the transform named by a `transform` option: `custom` for
+/// the error itself, or any expression evaluating to a `ResponseTransform`,
+/// such as a unit struct or a constant.
+fn transform_reference(expr: syn::Expr) -> proc_macro2::TokenStream {
+  match &expr {
+    syn::Expr::Path(syn::ExprPath { path, .. }) if path.is_ident("custom") => quote! { self },
+    _ => quote! { &(#expr) },
+  }
+}
+
 fn get_details(tokens: &mut Peekable<IntoIter>) -> Option<proc_macro2::TokenStream> {
   match tokens.peek() {
     Some(TokenTree::Literal(_)) => get_string(tokens).map(|tokens| tokens.into()),
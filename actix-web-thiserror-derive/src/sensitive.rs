@@ -0,0 +1,325 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote, ToTokens as _};
+
+/// What sensitive fields are replaced with in logs and details.
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Finds the fields of a variant marked with `#[response(sensitive)]`, in order.
+pub fn get_sensitive_fields(variant: &syn::Variant) -> Vec<bool> {
+  variant
+    .fields
+    .iter()
+    .map(|field| {
+      field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("response"))
+        .fold(false, |sensitive, attr| {
+          let mut field_sensitive = sensitive;
+
+          attr
+            .parse_nested_meta(|meta| {
+              if meta.path.is_ident("sensitive") {
+                field_sensitive = true;
+
+                Ok(())
+              } else {
+                Err(meta.error("unknown option"))
+              }
+            })
+            .unwrap_or_else(|_| {
+              panic!(
+                "Invalid #[response] options on field of {}: only `sensitive` is supported",
+                &variant.ident
+              )
+            });
+
+          field_sensitive
+        })
+    })
+    .collect()
+}
+
+fn field_binding(index: usize, field: &syn::Field) -> syn::Ident {
+  match &field.ident {
+    Some(ident) => format_ident!("__field_{}", ident.to_string().trim_start_matches("r#")),
+    None => format_ident!("__field_{}", index),
+  }
+}
+
+/// The pattern binding every field of a variant to `__field_<name or index>`.
+fn variant_pattern(name: &syn::Ident, variant: &syn::Variant) -> TokenStream {
+  let ident = &variant.ident;
+  let bindings = variant
+    .fields
+    .iter()
+    .enumerate()
+    .map(|(index, field)| field_binding(index, field));
+
+  match &variant.fields {
+    syn::Fields::Named(fields) => {
+      let names = fields.named.iter().map(|field| &field.ident);
+
+      quote! { #name::#ident { #(#names: #bindings),* } }
+    }
+    syn::Fields::Unnamed(_) => quote! { #name::#ident(#(#bindings),*) },
+    syn::Fields::Unit => quote! { #name::#ident },
+  }
+}
+
+/// The field a variant forwards to, whose own sensitive fields are masked too.
+pub struct Forwarded<'a> {
+  pub member: &'a syn::Member,
+  /// Whether the field is known to implement `ThiserrorResponse`, rather than
+  /// found by `auto_forward`.
+  pub explicit: bool,
+}
+
+impl Forwarded<'_> {
+  /// The binding of the field in `variant_pattern`, and the call reading its
+  /// redacted text through `method` of `ThiserrorResponse`.
+  fn redacted(&self, variant: &syn::Variant, method: &str) -> Option<(usize, TokenStream)> {
+    let (index, field) = variant.fields.iter().enumerate().find(|(index, field)| {
+      match (self.member, &field.ident) {
+        (syn::Member::Named(member), Some(ident)) => member == ident,
+        (syn::Member::Unnamed(member), None) => member.index as usize == *index,
+        _ => false,
+      }
+    })?;
+    let binding = field_binding(index, field);
+    let method = format_ident!("{}", method);
+
+    let call = match self.explicit {
+      true => quote! { ::actix_web_thiserror::ThiserrorResponse::#method(#binding) },
+      false => quote! {{
+        #[allow(unused_imports)]
+        use ::actix_web_thiserror::__private::{ForwardFallback as _, ForwardResponse as _};
+
+        (&::actix_web_thiserror::__private::Forward(#binding)).#method()
+      }},
+    };
+
+    Some((index, call))
+  }
+}
+
+/// Builds the match arm formatting a variant like `#[derive(Debug)]` would,
+/// with its sensitive fields masked, as well as those of the error it forwards
+/// to. Variants with nothing to mask have no arm.
+pub fn get_redacted_debug_arm(
+  name: &syn::Ident,
+  variant: &syn::Variant,
+  sensitive: &[bool],
+  forward: Option<&Forwarded>,
+) -> Option<TokenStream> {
+  let any_sensitive = sensitive.iter().any(|sensitive| *sensitive);
+  let forwarded = forward.and_then(|forward| forward.redacted(variant, "redacted_debug"));
+
+  if !any_sensitive && forwarded.is_none() {
+    return None;
+  }
+
+  let pattern = variant_pattern(name, variant);
+  let variant_str = variant.ident.to_string();
+  let variant_str = variant_str.trim_start_matches("r#");
+  let forward_index = forwarded.as_ref().map(|(index, _)| *index);
+  let values = variant
+    .fields
+    .iter()
+    .enumerate()
+    .map(|(index, field)| {
+      let binding = field_binding(index, field);
+
+      match sensitive[index] {
+        true => quote! { &format_args!(#REDACTED) },
+        false if forward_index == Some(index) => quote! {
+          &::actix_web_thiserror::__private::DebugFn(&|f| match &__redacted_debug {
+            Some(debug) => f.write_str(debug),
+            None => ::std::fmt::Debug::fmt(#binding, f),
+          })
+        },
+        false => binding.into_token_stream(),
+      }
+    })
+    .collect::<Vec<_>>();
+
+  let body = match &variant.fields {
+    syn::Fields::Named(fields) => {
+      let names = fields
+        .named
+        .iter()
+        .map(|field| field.ident.as_ref().map(|ident| ident.to_string()));
+
+      quote! { f.debug_struct(#variant_str)#(.field(#names, #values))*.finish() }
+    }
+    syn::Fields::Unnamed(_) => quote! { f.debug_tuple(#variant_str)#(.field(#values))*.finish() },
+    syn::Fields::Unit => quote! { f.write_str(#variant_str) },
+  };
+
+  let (inner, redacted) = match forwarded {
+    Some((_, call)) => (
+      quote! { let __redacted_debug: Option<String> = #call; },
+      quote! { #any_sensitive || __redacted_debug.is_some() },
+    ),
+    None => (quote! {}, quote! { true }),
+  };
+
+  Some(quote! {
+    #[allow(unused_variables)]
+    #pattern => {
+      #inner
+
+      match #redacted {
+        true => Some(format!(
+          "{:?}",
+          ::actix_web_thiserror::__private::DebugFn(&|f| #body),
+        )),
+        false => None,
+      }
+    }
+  })
+}
+
+/// Builds the match arm formatting the `#[error("...")]` message of a variant
+/// with its sensitive fields masked, if the message refers to any of them, as
+/// well as those of the error it forwards to.
+///
+/// Messages that pass additional format arguments are masked entirely.
+pub fn get_redacted_display_arm(
+  name: &syn::Ident,
+  variant: &syn::Variant,
+  sensitive: &[bool],
+  forward: Option<&Forwarded>,
+) -> Option<TokenStream> {
+  let any_sensitive = sensitive.iter().any(|sensitive| *sensitive);
+  let forwarded = forward.and_then(|forward| forward.redacted(variant, "redacted_display"));
+
+  if !any_sensitive && forwarded.is_none() {
+    return None;
+  }
+
+  let pattern = variant_pattern(name, variant);
+  let error_args = variant
+    .attrs
+    .iter()
+    .filter(|attr| attr.path().is_ident("error"))
+    .find_map(|attr| attr.parse_args::<TokenStream>().ok())?;
+
+  // Transparent variants display the error they wrap.
+  if syn::parse2::<syn::Ident>(error_args.to_owned()).is_ok_and(|ident| ident == "transparent") {
+    let (_, call) = forwarded?;
+
+    return Some(quote! {
+      #[allow(unused_variables)]
+      #pattern => #call,
+    });
+  }
+
+  let (display, has_args) = syn::parse::Parser::parse2(
+    |input: syn::parse::ParseStream| {
+      let display = input.parse::<syn::LitStr>()?;
+      let args = input.parse::<TokenStream>()?;
+
+      Ok((display, !args.is_empty()))
+    },
+    error_args,
+  )
+  .ok()?;
+
+  if has_args {
+    return Some(match (any_sensitive, forwarded) {
+      (false, Some((_, call))) => quote! {
+        #[allow(unused_variables)]
+        #pattern => (#call as Option<String>).map(|_| #REDACTED.to_owned()),
+      },
+      _ => quote! {
+        #[allow(unused_variables)]
+        #pattern => Some(#REDACTED.to_owned()),
+      },
+    });
+  }
+
+  let bindings = variant
+    .fields
+    .iter()
+    .enumerate()
+    .map(|(index, field)| {
+      let key = field
+        .ident
+        .as_ref()
+        .map(|ident| ident.to_string().trim_start_matches("r#").to_owned())
+        .unwrap_or_else(|| index.to_string());
+
+      (key, index, field_binding(index, field), sensitive[index])
+    })
+    .collect::<Vec<_>>();
+
+  let forward_index = forwarded.as_ref().map(|(index, _)| *index);
+  let mut redacted = false;
+  let mut forward_displayed = false;
+  let mut format = String::new();
+  let display = display.value();
+  let mut chars = display.chars().peekable();
+
+  while let Some(c) = chars.next() {
+    format.push(c);
+
+    match c {
+      '{' if chars.peek() == Some(&'{') => format.push(chars.next().unwrap_or('{')),
+      '{' => {
+        let mut key = String::new();
+
+        while let Some(c) = chars.next_if(|c| c.is_alphanumeric() || *c == '_') {
+          key.push(c);
+        }
+
+        match bindings.iter().find(|(field, ..)| field == &key) {
+          Some((_, index, binding, sensitive)) => {
+            redacted |= sensitive;
+            forward_displayed |= !sensitive && forward_index == Some(*index);
+            format.push_str(&binding.to_string());
+          }
+          None => format.push_str(&key),
+        }
+      }
+      _ => {}
+    }
+  }
+
+  let masks = bindings
+    .iter()
+    .filter(|(.., sensitive)| *sensitive)
+    .map(|(_, _, binding, _)| quote! { let #binding = #REDACTED; });
+
+  let (inner, redacted) = match forwarded.filter(|_| forward_displayed) {
+    Some((index, call)) => {
+      let binding = &bindings[index].2;
+
+      (
+        quote! {
+          let __redacted_display: Option<String> = #call;
+          let #binding: &dyn ::std::fmt::Display = match &__redacted_display {
+            Some(display) => display,
+            None => #binding,
+          };
+        },
+        quote! { #redacted || __redacted_display.is_some() },
+      )
+    }
+    None if redacted => (quote! {}, quote! { true }),
+    None => return None,
+  };
+
+  Some(quote! {
+    #[allow(unused_variables)]
+    #pattern => {
+      #(#masks)*
+      #inner
+
+      match #redacted {
+        true => Some(format!(#format)),
+        false => None,
+      }
+    }
+  })
+}
@@ -0,0 +1,97 @@
+use actix_web::http::StatusCode;
+use actix_web::HttpResponse;
+
+use crate::registry::find_registered;
+use crate::ErrorContext;
+
+/// An [`anyhow::Error`](https://docs.rs/anyhow) responding like the first
+/// registered error in its chain.
+///
+/// The response is rendered by the outermost error of the chain found by
+/// [`find_registered`], which covers every non-generic enum deriving
+/// [`ResponseError`][response_error], so it uses the transform of that error.
+/// Errors registered by hand that do not implement
+/// [`actix_web::ResponseError`] are rendered with their status, reason, type and
+/// details by the global transform. Without one, the error responds with the
+/// default status code of the global transform and nothing else.
+///
+/// ```rust
+/// use actix_web::HttpResponse;
+/// use actix_web_thiserror::{AnyResponse, ResponseError};
+/// use anyhow::Context as _;
+/// use thiserror::Error;
+///
+/// #[derive(Debug, Error, ResponseError)]
+/// pub enum UserError {
+///   #[response(status = 404, reason = "USER_NOT_FOUND")]
+///   #[error("user not found")]
+///   NotFound,
+/// }
+///
+/// fn load_user() -> anyhow::Result<String> {
+///   Err(UserError::NotFound).context("loading the profile")
+/// }
+///
+/// pub async fn profile() -> Result<HttpResponse, AnyResponse> {
+///   Ok(HttpResponse::Ok().body(load_user()?))
+/// }
+/// ```
+///
+/// [response_error]: crate::ResponseError
+pub struct AnyResponse(pub anyhow::Error);
+
+impl<E: Into<anyhow::Error>> From<E> for AnyResponse {
+  fn from(err: E) -> Self {
+    Self(err.into())
+  }
+}
+
+impl std::fmt::Debug for AnyResponse {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    std::fmt::Debug::fmt(&self.0, f)
+  }
+}
+
+impl std::fmt::Display for AnyResponse {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    std::fmt::Display::fmt(&self.0, f)
+  }
+}
+
+impl actix_web::ResponseError for AnyResponse {
+  fn status_code(&self) -> StatusCode {
+    find_registered(self.0.as_ref())
+      .map(|registered| registered.status_code)
+      .unwrap_or_else(crate::default_global_error_status_code)
+  }
+
+  fn error_response(&self) -> HttpResponse {
+    let err: &(dyn std::error::Error + 'static) = self.0.as_ref();
+    let registered = find_registered(err);
+
+    // Registered errors render themselves, with their own transform and
+    // sensitive fields.
+    if let Some(response) = registered
+      .as_ref()
+      .and_then(|registered| crate::registry::error_response(registered.err))
+    {
+      return response;
+    }
+
+    let context = match registered {
+      Some(registered) => {
+        let mut context = ErrorContext::new(registered.name, err, registered.status_code);
+        context.variant = registered.variant;
+        context.set_meta(registered.meta);
+        context
+      }
+      None => ErrorContext::new(
+        "anyhow::Error",
+        err,
+        crate::default_global_error_status_code(),
+      ),
+    };
+
+    crate::respond(&context, &self.0)
+  }
+}
@@ -0,0 +1,125 @@
+use actix_web::http::StatusCode;
+use actix_web::HttpResponse;
+use serde::Serialize;
+
+use crate::{ErrorContext, ResponseMeta, ThiserrorResponse};
+
+/// An error built at runtime, for one-off responses that do not deserve a
+/// variant of their own.
+///
+/// It is rendered by the global transform and logged like derived errors, and
+/// can be forwarded to from derived enums.
+///
+/// ```rust
+/// use actix_web::http::StatusCode;
+/// use actix_web::HttpResponse;
+/// use actix_web_thiserror::ApiError;
+/// use serde_json::json;
+///
+/// pub async fn update() -> Result<HttpResponse, ApiError> {
+///   Err(
+///     ApiError::new(StatusCode::CONFLICT)
+///       .reason("VERSION_MISMATCH")
+///       .details(json!({ "expected": 3, "found": 2 })),
+///   )
+/// }
+/// ```
+#[derive(Clone)]
+pub struct ApiError {
+  status_code: StatusCode,
+  meta: ResponseMeta,
+  message: Option<String>,
+}
+
+impl ApiError {
+  /// Creates an error with the given status code and nothing else.
+  pub fn new(status_code: StatusCode) -> Self {
+    Self {
+      status_code,
+      meta: ResponseMeta::new().status_code(status_code),
+      message: None,
+    }
+  }
+
+  /// Sets the `reason`, omitting it when it fails to serialize.
+  pub fn reason(mut self, reason: impl Serialize) -> Self {
+    self.meta = self.meta.reason(reason);
+    self
+  }
+
+  /// Sets the `type`.
+  pub fn _type(mut self, _type: impl Into<String>) -> Self {
+    self.meta = self.meta._type(_type);
+    self
+  }
+
+  /// Sets the `details`, omitting them when they fail to serialize.
+  pub fn details(mut self, details: impl Serialize) -> Self {
+    self.meta = self.meta.details(details);
+    self
+  }
+
+  /// Sets the message that is safe to show to clients.
+  pub fn public_message(mut self, public_message: impl Into<String>) -> Self {
+    self.meta = self.meta.public_message(public_message);
+    self
+  }
+
+  /// Sets the `Display` text of the error, which is logged and shown to
+  /// clients in development. It defaults to the canonical reason of the status
+  /// code.
+  pub fn message(mut self, message: impl Into<String>) -> Self {
+    self.message = Some(message.into());
+    self
+  }
+}
+
+impl std::fmt::Debug for ApiError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("ApiError")
+      .field("status_code", &self.status_code)
+      .field("reason", &self.meta.reason)
+      .field("type", &self.meta._type)
+      .field("details", &self.meta.details)
+      .field("message", &self.message)
+      .finish()
+  }
+}
+
+impl std::fmt::Display for ApiError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match &self.message {
+      Some(message) => f.write_str(message),
+      None => f.write_str(
+        self
+          .status_code
+          .canonical_reason()
+          .unwrap_or("Unknown error"),
+      ),
+    }
+  }
+}
+
+impl std::error::Error for ApiError {}
+
+#[cfg(feature = "registry")]
+crate::register_response!(ApiError);
+
+impl ThiserrorResponse for ApiError {
+  fn response_meta(&self) -> ResponseMeta {
+    self.meta.to_owned()
+  }
+}
+
+impl actix_web::ResponseError for ApiError {
+  fn status_code(&self) -> StatusCode {
+    self.status_code
+  }
+
+  fn error_response(&self) -> HttpResponse {
+    let mut context = ErrorContext::new("ApiError", self, self.status_code);
+    context.set_meta(self.meta.to_owned());
+
+    crate::respond(&context, &self.meta)
+  }
+}
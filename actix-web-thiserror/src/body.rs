@@ -0,0 +1,334 @@
+use actix_web::http::StatusCode;
+use actix_web::HttpResponse;
+use serde::Serialize;
+
+#[cfg(feature = "openapi")]
+use crate::ErrorCatalogEntry;
+use crate::{ErrorContext, ErrorResponseParts, ResponseTransform};
+
+/// A transform that only describes the body of error responses.
+///
+/// Every `BodyTransform` is a [`ResponseTransform`]: the body is serialized into
+/// JSON and sent with the status code of the error and an `application/json`
+/// content type.
+///
+/// ```rust
+/// # use actix_web_thiserror::{BodyTransform, ErrorContext};
+/// # use serde_json::json;
+/// struct ProblemTransform;
+///
+/// impl BodyTransform for ProblemTransform {
+///   fn body(&self, context: &ErrorContext) -> impl serde::Serialize {
+///     json!({
+///       "title": context.reason,
+///       "status": context.status_code.as_u16(),
+///       "detail": context.message(),
+///     })
+///   }
+/// }
+///
+/// actix_web_thiserror::set_global_transform(ProblemTransform);
+/// ```
+pub trait BodyTransform {
+  fn body(&self, context: &ErrorContext) -> impl Serialize;
+
+  fn default_error_status_code(&self) -> StatusCode {
+    StatusCode::INTERNAL_SERVER_ERROR
+  }
+
+  /// The schema of the bodies this transform produces, see
+  /// [`ResponseTransform::openapi_schema`].
+  #[cfg(feature = "openapi")]
+  fn openapi_schema(&self) -> Option<utoipa::openapi::RefOr<utoipa::openapi::schema::Schema>> {
+    None
+  }
+
+  /// The body this transform would produce for a variant, see
+  /// [`ResponseTransform::openapi_example`].
+  #[cfg(feature = "openapi")]
+  #[allow(unused_variables)]
+  fn openapi_example(&self, entry: &ErrorCatalogEntry) -> Option<serde_json::Value> {
+    None
+  }
+}
+
+impl<T: BodyTransform> ResponseTransform for T {
+  fn transform(
+    &self,
+    name: &str,
+    err: &dyn std::error::Error,
+    status_code: StatusCode,
+    reason: Option<serde_json::Value>,
+    _type: Option<String>,
+    details: Option<serde_json::Value>,
+  ) -> HttpResponse {
+    self.transform_with_context(&ErrorContext::from_parts(
+      name,
+      err,
+      status_code,
+      reason,
+      _type,
+      details,
+    ))
+  }
+
+  fn transform_with_context(&self, context: &ErrorContext) -> HttpResponse {
+    HttpResponse::build(context.status_code).json(self.body(context))
+  }
+
+  fn default_error_status_code(&self) -> StatusCode {
+    BodyTransform::default_error_status_code(self)
+  }
+
+  #[cfg(feature = "openapi")]
+  fn openapi_schema(&self) -> Option<utoipa::openapi::RefOr<utoipa::openapi::schema::Schema>> {
+    BodyTransform::openapi_schema(self)
+  }
+
+  #[cfg(feature = "openapi")]
+  fn openapi_example(&self, entry: &ErrorCatalogEntry) -> Option<serde_json::Value> {
+    BodyTransform::openapi_example(self, entry)
+  }
+}
+
+/// The body produced by [`JsonTransform`].
+#[derive(Debug, Serialize)]
+pub struct JsonBody {
+  /// Always `0`, marking the request as failed.
+  pub result: u8,
+  /// The `reason` of the error.
+  pub reason: Option<serde_json::Value>,
+  /// The `type` of the error, if any.
+  #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+  pub _type: Option<String>,
+  /// The `details` of the error, if any.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub details: Option<serde_json::Value>,
+  /// The message exposed to clients, see [`ErrorContext::message`].
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub message: Option<String>,
+}
+
+/// A [`BodyTransform`] rendering errors as `{"result": 0, "reason": ...}`,
+/// along with their `type`, `details` and exposed message when they have any.
+///
+/// With the `openapi` feature enabled, it documents the schema of this body and
+/// an example for each variant.
+///
+/// See [`LegacyResultTransform`] to pick the keys.
+pub struct JsonTransform;
+
+impl BodyTransform for JsonTransform {
+  fn body(&self, context: &ErrorContext) -> impl Serialize {
+    JsonBody {
+      result: 0,
+      reason: context.reason.to_owned(),
+      _type: context._type.to_owned(),
+      details: context.details.to_owned(),
+      message: context.message(),
+    }
+  }
+
+  #[cfg(feature = "openapi")]
+  fn openapi_schema(&self) -> Option<utoipa::openapi::RefOr<utoipa::openapi::schema::Schema>> {
+    BodyTransform::openapi_schema(&JsonTransform::as_legacy())
+  }
+
+  #[cfg(feature = "openapi")]
+  fn openapi_example(&self, entry: &ErrorCatalogEntry) -> Option<serde_json::Value> {
+    BodyTransform::openapi_example(&JsonTransform::as_legacy(), entry)
+  }
+}
+
+#[cfg(feature = "openapi")]
+impl JsonTransform {
+  /// The [`LegacyResultTransform`] producing the same bodies.
+  fn as_legacy() -> LegacyResultTransform {
+    LegacyResultTransform::new()
+      .type_key("type")
+      .details_key("details")
+      .message_key("message")
+  }
+}
+
+/// A [`BodyTransform`] rendering errors as `{"result": 0, "reason": ...}`, with
+/// configurable key names.
+///
+/// The `type`, `details` and exposed message of errors are left out unless a
+/// key is given for them, and are only written when the error has one.
+///
+/// With the `openapi` feature enabled, the schema and examples it documents use
+/// the same keys.
+///
+/// ```rust
+/// # use actix_web_thiserror::LegacyResultTransform;
+/// // {"success": false, "code": "INVALID_IMAGE_FORMAT", "message": "..."}
+/// actix_web_thiserror::set_global_transform(
+///   LegacyResultTransform::new()
+///     .result("success", false)
+///     .reason_key("code")
+///     .message_key("message"),
+/// );
+/// ```
+#[derive(Clone, Debug)]
+pub struct LegacyResultTransform {
+  result_key: String,
+  result: serde_json::Value,
+  reason_key: String,
+  type_key: Option<String>,
+  details_key: Option<String>,
+  message_key: Option<String>,
+}
+
+impl Default for LegacyResultTransform {
+  fn default() -> Self {
+    Self {
+      result_key: "result".to_owned(),
+      result: serde_json::Value::from(0),
+      reason_key: "reason".to_owned(),
+      type_key: None,
+      details_key: None,
+      message_key: None,
+    }
+  }
+}
+
+impl LegacyResultTransform {
+  /// Creates a transform producing `{"result": 0, "reason": ...}`.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Sets the key and value marking the request as failed.
+  pub fn result(mut self, key: impl Into<String>, value: impl Into<serde_json::Value>) -> Self {
+    self.result_key = key.into();
+    self.result = value.into();
+    self
+  }
+
+  /// Sets the key of the `reason`.
+  pub fn reason_key(mut self, key: impl Into<String>) -> Self {
+    self.reason_key = key.into();
+    self
+  }
+
+  /// Writes the `type` of errors under `key`.
+  pub fn type_key(mut self, key: impl Into<String>) -> Self {
+    self.type_key = Some(key.into());
+    self
+  }
+
+  /// Writes the `details` of errors under `key`.
+  pub fn details_key(mut self, key: impl Into<String>) -> Self {
+    self.details_key = Some(key.into());
+    self
+  }
+
+  /// Writes the message exposed to clients under `key`, see
+  /// [`ErrorContext::message`].
+  pub fn message_key(mut self, key: impl Into<String>) -> Self {
+    self.message_key = Some(key.into());
+    self
+  }
+}
+
+impl LegacyResultTransform {
+  /// Reads a body produced by this transform.
+  ///
+  /// Keys that were not configured fall back to `type` and `details`.
+  pub fn parts(&self, status_code: StatusCode, body: &[u8]) -> Option<ErrorResponseParts> {
+    ErrorResponseParts::from_json_with_keys(
+      status_code,
+      body,
+      &self.reason_key,
+      self.type_key.as_deref().unwrap_or("type"),
+      self.details_key.as_deref().unwrap_or("details"),
+    )
+  }
+}
+
+impl BodyTransform for LegacyResultTransform {
+  fn body(&self, context: &ErrorContext) -> impl Serialize {
+    let mut body = serde_json::Map::new();
+    body.insert(self.result_key.to_owned(), self.result.to_owned());
+    body.insert(
+      self.reason_key.to_owned(),
+      context.reason.to_owned().unwrap_or_default(),
+    );
+
+    let optional = [
+      (
+        &self.type_key,
+        context._type.to_owned().map(serde_json::Value::from),
+      ),
+      (&self.details_key, context.details.to_owned()),
+      (
+        &self.message_key,
+        context.message().map(serde_json::Value::from),
+      ),
+    ];
+
+    for (key, value) in optional {
+      if let (Some(key), Some(value)) = (key, value) {
+        body.insert(key.to_owned(), value);
+      }
+    }
+
+    body
+  }
+
+  #[cfg(feature = "openapi")]
+  fn openapi_schema(&self) -> Option<utoipa::openapi::RefOr<utoipa::openapi::schema::Schema>> {
+    use utoipa::openapi::schema::{ObjectBuilder, SchemaType, Type};
+
+    let result_type = match &self.result {
+      serde_json::Value::Bool(_) => SchemaType::Type(Type::Boolean),
+      serde_json::Value::Number(result) if result.is_f64() => SchemaType::Type(Type::Number),
+      serde_json::Value::Number(_) => SchemaType::Type(Type::Integer),
+      serde_json::Value::String(_) => SchemaType::Type(Type::String),
+      _ => SchemaType::AnyValue,
+    };
+
+    let mut schema = ObjectBuilder::new()
+      .property(
+        &self.result_key,
+        ObjectBuilder::new().schema_type(result_type),
+      )
+      .required(&self.result_key)
+      .property(
+        &self.reason_key,
+        ObjectBuilder::new().schema_type(SchemaType::AnyValue),
+      )
+      .required(&self.reason_key);
+
+    let optional = [
+      (&self.type_key, SchemaType::Type(Type::String)),
+      (&self.details_key, SchemaType::AnyValue),
+      (&self.message_key, SchemaType::Type(Type::String)),
+    ];
+
+    for (key, schema_type) in optional {
+      if let Some(key) = key {
+        schema = schema.property(key, ObjectBuilder::new().schema_type(schema_type));
+      }
+    }
+
+    Some(schema.into())
+  }
+
+  #[cfg(feature = "openapi")]
+  fn openapi_example(&self, entry: &ErrorCatalogEntry) -> Option<serde_json::Value> {
+    let mut example = serde_json::Map::new();
+    example.insert(self.result_key.to_owned(), self.result.to_owned());
+    example.insert(
+      self.reason_key.to_owned(),
+      entry.reason.to_owned().unwrap_or_default(),
+    );
+
+    if let (Some(key), Some(_type)) = (&self.type_key, entry._type) {
+      example.insert(key.to_owned(), serde_json::Value::from(_type));
+    }
+
+    Some(serde_json::Value::Object(example))
+  }
+}
@@ -0,0 +1,155 @@
+use std::fmt::Write as _;
+
+use serde::Serialize;
+
+/// Every error an enum deriving [`ResponseError`][response_error] can emit, as
+/// declared in its `#[response]` and `#[error]` attributes.
+///
+/// [response_error]: crate::ResponseError
+#[derive(Clone, Debug, Serialize)]
+pub struct ErrorCatalog {
+  /// The name of the error enum.
+  pub name: &'static str,
+  /// One entry per variant, in declaration order.
+  pub entries: Vec<ErrorCatalogEntry>,
+}
+
+/// A single variant of an [`ErrorCatalog`].
+#[derive(Clone, Debug, Serialize)]
+pub struct ErrorCatalogEntry {
+  /// The name of the variant.
+  pub variant: &'static str,
+  /// The explicit `status`, if any. Variants without one respond with the
+  /// transform's default status code.
+  pub status: Option<u16>,
+  /// The `reason`, serialized as it is handed to the transform.
+  pub reason: Option<serde_json::Value>,
+  /// The `type`, if any.
+  #[serde(rename = "type")]
+  pub _type: Option<&'static str>,
+  /// The `#[error("...")]` format string, if the variant is not transparent.
+  pub display: Option<&'static str>,
+  /// The `details` format string, if any.
+  pub details: Option<&'static str>,
+  /// The type of the field serialized into `details`, when it is `"{0}"`.
+  pub details_type: Option<&'static str>,
+  /// Whether the variant forwards its response to the wrapped error.
+  pub forward: bool,
+  /// The default status code of the transform the variant is rendered with,
+  /// when the enum or the variant sets one with `#[response(transform = ...)]`.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub transform_status: Option<u16>,
+}
+
+impl ErrorCatalogEntry {
+  /// The status code the variant responds with, falling back to the default of
+  /// its transform, or of the global transform, when the variant does not set
+  /// one.
+  ///
+  /// Forwarded variants report the default as well, as their status depends on
+  /// the wrapped error.
+  pub fn status_code(&self) -> actix_web::http::StatusCode {
+    self
+      .status
+      .or(self.transform_status)
+      .and_then(|status| actix_web::http::StatusCode::from_u16(status).ok())
+      .unwrap_or_else(crate::default_global_error_status_code)
+  }
+}
+
+impl ErrorCatalog {
+  /// Serializes the catalog into JSON.
+  pub fn to_json(&self) -> serde_json::Value {
+    serde_json::to_value(self).unwrap_or_default()
+  }
+
+  /// Renders the catalog as a Markdown section with one table row per variant.
+  pub fn to_markdown(&self) -> String {
+    let mut markdown = format!(
+      "## {}\n\n| Variant | Status | Reason | Type | Message | Details |\n| --- | --- | --- | --- | --- | --- |\n",
+      self.name
+    );
+
+    for entry in &self.entries {
+      let status = match entry.forward {
+        true => "forwarded".to_owned(),
+        false => entry.status_code().as_u16().to_string(),
+      };
+      let reason = entry
+        .reason
+        .as_ref()
+        .map(|reason| format!("`{}`", reason))
+        .unwrap_or_default();
+      let details = match (entry.details, entry.details_type) {
+        (Some(details), Some(details_type)) => format!("`{}`: `{}`", details, details_type),
+        (Some(details), None) => format!("`{}`", details),
+        _ => String::new(),
+      };
+
+      let _ = writeln!(
+        markdown,
+        "| `{}` | {} | {} | {} | {} | {} |",
+        entry.variant,
+        status,
+        reason,
+        entry
+          ._type
+          .map(|_type| format!("`{}`", _type))
+          .unwrap_or_default(),
+        entry.display.unwrap_or_default().replace('|', "\\|"),
+        details,
+      );
+    }
+
+    markdown
+  }
+}
+
+/// Gives access to the [`ErrorCatalog`] of an error enum.
+///
+/// This is implemented by `#[derive(ResponseError)]`.
+pub trait ThiserrorCatalog {
+  fn error_catalog() -> &'static ErrorCatalog;
+}
+
+#[cfg(feature = "catalog")]
+#[doc(hidden)]
+pub struct ErrorCatalogRegistration(pub fn() -> &'static ErrorCatalog);
+
+#[cfg(feature = "catalog")]
+inventory::collect!(ErrorCatalogRegistration);
+
+/// Returns the catalogs of every error enum deriving
+/// [`ResponseError`][response_error] that is linked into the binary, sorted by
+/// name.
+///
+/// [response_error]: crate::ResponseError
+#[cfg(feature = "catalog")]
+pub fn error_catalogs() -> Vec<&'static ErrorCatalog> {
+  let mut catalogs = inventory::iter::<ErrorCatalogRegistration>
+    .into_iter()
+    .map(|registration| (registration.0)())
+    .collect::<Vec<_>>();
+
+  catalogs.sort_by_key(|catalog| catalog.name);
+
+  catalogs
+}
+
+#[cfg(feature = "catalog")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __submit_error_catalog {
+  ($catalog:path) => {
+    $crate::__private::inventory::submit! {
+      $crate::ErrorCatalogRegistration($catalog)
+    }
+  };
+}
+
+#[cfg(not(feature = "catalog"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __submit_error_catalog {
+  ($catalog:path) => {};
+}
@@ -0,0 +1,95 @@
+use actix_web::http::StatusCode;
+
+use crate::{ErrorCatalog, ErrorCatalogEntry};
+
+/// What a client knows about an error response produced by this crate.
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct ErrorResponseParts {
+  /// The status code of the response.
+  pub status_code: StatusCode,
+  /// The `reason` of the error, if any.
+  pub reason: Option<serde_json::Value>,
+  /// The `type` of the error, if any.
+  pub _type: Option<String>,
+  /// The `details` of the error, if any.
+  pub details: Option<serde_json::Value>,
+}
+
+impl ErrorResponseParts {
+  /// Creates parts with only a status code.
+  pub fn new(status_code: StatusCode) -> Self {
+    Self {
+      status_code,
+      reason: None,
+      _type: None,
+      details: None,
+    }
+  }
+
+  /// Reads a JSON body with `reason`, `type` and `details` keys, as produced by
+  /// [`JsonTransform`][crate::JsonTransform].
+  pub fn from_json(status_code: StatusCode, body: &[u8]) -> Option<Self> {
+    Self::from_json_with_keys(status_code, body, "reason", "type", "details")
+  }
+
+  /// Reads a JSON body holding the `reason`, `type` and `details` of an error
+  /// under the given keys.
+  pub fn from_json_with_keys(
+    status_code: StatusCode,
+    body: &[u8],
+    reason_key: &str,
+    type_key: &str,
+    details_key: &str,
+  ) -> Option<Self> {
+    let serde_json::Value::Object(mut body) = serde_json::from_slice(body).ok()? else {
+      return None;
+    };
+
+    Some(Self {
+      status_code,
+      reason: body.remove(reason_key).filter(|reason| !reason.is_null()),
+      _type: body
+        .remove(type_key)
+        .and_then(|_type| _type.as_str().map(|_type| _type.to_owned())),
+      details: body.remove(details_key),
+    })
+  }
+
+  /// Whether the response could have been produced by the variant of `entry`:
+  /// its reason matches, as does its status code when it declares one.
+  pub fn matches(&self, entry: &ErrorCatalogEntry) -> bool {
+    entry.reason.is_some()
+      && entry.reason == self.reason
+      && entry
+        .status
+        .is_none_or(|status| status == self.status_code.as_u16())
+  }
+
+  /// The names of the variants of `catalog` that could have produced the
+  /// response, in declaration order.
+  pub fn matching_variants<'a>(
+    &'a self,
+    catalog: &'a ErrorCatalog,
+  ) -> impl Iterator<Item = &'static str> + 'a {
+    catalog
+      .entries
+      .iter()
+      .filter(move |entry| !entry.forward && self.matches(entry))
+      .map(|entry| entry.variant)
+  }
+}
+
+/// Reconstructs an error from a response produced by this crate, typically on
+/// the client side of a service to service call.
+///
+/// This is implemented by `#[derive(FromErrorResponse)]`.
+pub trait FromErrorResponse: Sized {
+  fn from_parts(parts: &ErrorResponseParts) -> Option<Self>;
+
+  /// Reconstructs an error from a status code and a JSON body, see
+  /// [`ErrorResponseParts::from_json`].
+  fn from_error_response(status_code: StatusCode, body: &[u8]) -> Option<Self> {
+    Self::from_parts(&ErrorResponseParts::from_json(status_code, body)?)
+  }
+}
@@ -0,0 +1,106 @@
+use crate::{exposure, Exposure, ResponseMeta};
+
+/// Everything known about an error as it is turned into a response.
+#[derive(Clone)]
+#[non_exhaustive]
+pub struct ErrorContext<'a> {
+  /// The name of the error type.
+  pub name: &'a str,
+  /// The name of the variant, empty when the error is not an enum.
+  pub variant: &'a str,
+  /// The error itself.
+  pub err: &'a dyn std::error::Error,
+  /// The status code of the response.
+  pub status_code: actix_web::http::StatusCode,
+  /// The id correlating the response with its log record, see
+  /// [`RequestId`][crate::RequestId].
+  pub correlation_id: String,
+  /// The `reason` of the error, if any.
+  pub reason: Option<serde_json::Value>,
+  /// The `type` of the error, if any.
+  pub _type: Option<String>,
+  /// The `details` of the error, if any.
+  pub details: Option<serde_json::Value>,
+  /// The message that is safe to show to clients, from
+  /// `#[response(public = "...")]` or `#[response(expose)]`.
+  pub public_message: Option<String>,
+  /// The `Display` text of the error with its `#[response(sensitive)]` fields
+  /// masked, when it differs from the original.
+  pub redacted_display: Option<String>,
+}
+
+impl<'a> ErrorContext<'a> {
+  /// Creates a context without any response metadata, for the correlation id
+  /// of the current request.
+  pub fn new(
+    name: &'a str,
+    err: &'a dyn std::error::Error,
+    status_code: actix_web::http::StatusCode,
+  ) -> Self {
+    Self {
+      name,
+      variant: "",
+      err,
+      status_code,
+      correlation_id: crate::correlation_id(),
+      reason: None,
+      _type: None,
+      details: None,
+      public_message: None,
+      redacted_display: None,
+    }
+  }
+
+  /// Creates a context from the arguments of [`ResponseTransform::transform`],
+  /// for transforms that only implement `transform_with_context`.
+  ///
+  /// [`ResponseTransform::transform`]: crate::ResponseTransform::transform
+  pub(crate) fn from_parts(
+    name: &'a str,
+    err: &'a dyn std::error::Error,
+    status_code: actix_web::http::StatusCode,
+    reason: Option<serde_json::Value>,
+    _type: Option<String>,
+    details: Option<serde_json::Value>,
+  ) -> Self {
+    Self {
+      reason,
+      _type,
+      details,
+      ..Self::new(name, err, status_code)
+    }
+  }
+
+  /// Sets the reason, type, details and public message from `meta`.
+  pub(crate) fn set_meta(&mut self, meta: ResponseMeta) {
+    self.reason = meta.reason.into_option();
+    self._type = meta._type.into_option();
+    self.details = meta.details.into_option();
+    self.public_message = meta.public_message.into_option();
+  }
+
+  /// The message to show to clients under the current [`Exposure`].
+  ///
+  /// In production this is the public message, if the error has one. In
+  /// development it is the `Display` text of the error, with sensitive fields
+  /// masked, followed by its source chain.
+  pub fn message(&self) -> Option<String> {
+    match exposure() {
+      Exposure::Production => self.public_message.to_owned(),
+      Exposure::Development => {
+        let mut message = self
+          .redacted_display
+          .to_owned()
+          .unwrap_or_else(|| self.err.to_string());
+        let mut source = self.err.source();
+
+        while let Some(err) = source {
+          message = format!("{}: {}", message, err);
+          source = err.source();
+        }
+
+        Some(message)
+      }
+    }
+  }
+}
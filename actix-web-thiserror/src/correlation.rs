@@ -0,0 +1,101 @@
+use std::collections::hash_map::RandomState;
+use std::future::{ready, Future, Ready};
+use std::hash::{BuildHasher as _, Hasher as _};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use actix_web::dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform};
+use actix_web::http::header::HeaderName;
+
+/// The header the [`RequestId`] middleware reads correlation ids from.
+pub const REQUEST_ID_HEADER: HeaderName = HeaderName::from_static("x-request-id");
+
+const MAX_REQUEST_ID_LEN: usize = 128;
+
+tokio::task_local! {
+  static CORRELATION_ID: String;
+}
+
+static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+fn new_correlation_id() -> String {
+  let state = RandomState::new();
+  let counter = COUNTER.fetch_add(1, Ordering::Relaxed);
+
+  let mut high = state.build_hasher();
+  high.write_u64(counter);
+
+  let mut low = state.build_hasher();
+  low.write_u64(high.finish());
+  low.write_u64(counter);
+
+  format!("{:016x}{:016x}", high.finish(), low.finish())
+}
+
+/// Returns the correlation id of the current request.
+///
+/// Within the [`RequestId`] middleware this is the id of the request, so every
+/// error it produces shares it. Elsewhere, a new id is generated on each call.
+pub fn correlation_id() -> String {
+  CORRELATION_ID
+    .try_with(|correlation_id| correlation_id.to_owned())
+    .unwrap_or_else(|_| new_correlation_id())
+}
+
+/// Middleware assigning a correlation id to each request, taken from its
+/// `X-Request-Id` header or generated when it is missing or malformed.
+///
+/// The id is included in the log record of every error response and handed
+/// to the transform through [`ErrorContext::correlation_id`][context].
+///
+/// [context]: crate::ErrorContext::correlation_id
+pub struct RequestId;
+
+impl<S, B> Transform<S, ServiceRequest> for RequestId
+where
+  S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = actix_web::Error>,
+  S::Future: 'static,
+{
+  type Response = ServiceResponse<B>;
+  type Error = actix_web::Error;
+  type Transform = RequestIdMiddleware<S>;
+  type InitError = ();
+  type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+  fn new_transform(&self, service: S) -> Self::Future {
+    ready(Ok(RequestIdMiddleware { service }))
+  }
+}
+
+#[doc(hidden)]
+pub struct RequestIdMiddleware<S> {
+  service: S,
+}
+
+impl<S, B> Service<ServiceRequest> for RequestIdMiddleware<S>
+where
+  S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = actix_web::Error>,
+  S::Future: 'static,
+{
+  type Response = ServiceResponse<B>;
+  type Error = actix_web::Error;
+  type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>>>>;
+
+  forward_ready!(service);
+
+  fn call(&self, req: ServiceRequest) -> Self::Future {
+    let correlation_id = req
+      .headers()
+      .get(REQUEST_ID_HEADER)
+      .and_then(|value| value.to_str().ok())
+      .filter(|value| {
+        !value.is_empty()
+          && value.len() <= MAX_REQUEST_ID_LEN
+          && value.bytes().all(|byte| byte.is_ascii_graphic())
+      })
+      .map(|value| value.to_owned())
+      .unwrap_or_else(new_correlation_id);
+
+    Box::pin(CORRELATION_ID.scope(correlation_id, self.service.call(req)))
+  }
+}
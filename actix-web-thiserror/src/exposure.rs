@@ -0,0 +1,29 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static DEVELOPMENT: AtomicBool = AtomicBool::new(false);
+
+/// How much of an error's message the built-in transforms may show to clients.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Exposure {
+  /// Only messages marked with `#[response(public = "...")]` or
+  /// `#[response(expose)]` are rendered.
+  #[default]
+  Production,
+  /// The full `Display` text and source chain of every error is rendered.
+  Development,
+}
+
+/// Sets how much of an error's message is exposed to clients.
+///
+/// Defaults to [`Exposure::Production`].
+pub fn set_exposure(exposure: Exposure) {
+  DEVELOPMENT.store(exposure == Exposure::Development, Ordering::Relaxed);
+}
+
+/// Returns the current [`Exposure`].
+pub fn exposure() -> Exposure {
+  match DEVELOPMENT.load(Ordering::Relaxed) {
+    true => Exposure::Development,
+    false => Exposure::Production,
+  }
+}
@@ -0,0 +1,162 @@
+//! Rendering the errors of actix extractors through the transform.
+//!
+//! By default, a malformed JSON body, query string, path or form is answered
+//! by actix with a plain-text response of its own. [`configure`] installs error
+//! handlers turning them into an [`ExtractorError`], which is rendered, logged
+//! and observed like derived errors.
+//!
+//! ```rust
+//! use actix_web::App;
+//!
+//! let app = App::new().configure(actix_web_thiserror::extractors::configure);
+//! ```
+//!
+//! `PayloadConfig` takes no error handler, so the errors of the `Bytes` and
+//! `String` extractors keep the responses of actix.
+
+use actix_web::error::{JsonPayloadError, PathError, QueryPayloadError, UrlencodedError};
+use actix_web::http::StatusCode;
+use actix_web::{web, HttpRequest, HttpResponse, ResponseError as _};
+
+use crate::{ErrorContext, ResponseMeta, ThiserrorResponse};
+
+/// An error of an actix extractor.
+///
+/// Its reason is one of `PAYLOAD_TOO_LARGE`, `UNSUPPORTED_MEDIA_TYPE` and
+/// `LENGTH_REQUIRED`, with the matching status code, or one of `INVALID_JSON`,
+/// `INVALID_QUERY`, `INVALID_PATH` and `INVALID_FORM`, with the status code
+/// actix would respond with.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ExtractorError {
+  /// From [`Json`][web::Json].
+  Json(JsonPayloadError),
+  /// From [`Query`][web::Query].
+  Query(QueryPayloadError),
+  /// From [`Path`][web::Path].
+  Path(PathError),
+  /// From [`Form`][web::Form].
+  Form(UrlencodedError),
+}
+
+impl ExtractorError {
+  /// The name of the variant.
+  pub fn variant(&self) -> &'static str {
+    match self {
+      Self::Json(_) => "Json",
+      Self::Query(_) => "Query",
+      Self::Path(_) => "Path",
+      Self::Form(_) => "Form",
+    }
+  }
+
+  /// The `reason` of the error.
+  pub fn reason(&self) -> &'static str {
+    self.response().1
+  }
+
+  fn response(&self) -> (StatusCode, &'static str) {
+    match self {
+      Self::Json(
+        JsonPayloadError::OverflowKnownLength { .. } | JsonPayloadError::Overflow { .. },
+      )
+      | Self::Form(UrlencodedError::Overflow { .. }) => {
+        (StatusCode::PAYLOAD_TOO_LARGE, "PAYLOAD_TOO_LARGE")
+      }
+      Self::Json(JsonPayloadError::ContentType) | Self::Form(UrlencodedError::ContentType) => {
+        (StatusCode::UNSUPPORTED_MEDIA_TYPE, "UNSUPPORTED_MEDIA_TYPE")
+      }
+      Self::Form(UrlencodedError::UnknownLength) => {
+        (StatusCode::LENGTH_REQUIRED, "LENGTH_REQUIRED")
+      }
+      Self::Json(err) => (err.status_code(), "INVALID_JSON"),
+      Self::Query(err) => (err.status_code(), "INVALID_QUERY"),
+      Self::Path(err) => (err.status_code(), "INVALID_PATH"),
+      Self::Form(err) => (err.status_code(), "INVALID_FORM"),
+    }
+  }
+
+  fn inner(&self) -> &(dyn actix_web::ResponseError + 'static) {
+    match self {
+      Self::Json(err) => err,
+      Self::Query(err) => err,
+      Self::Path(err) => err,
+      Self::Form(err) => err,
+    }
+  }
+}
+
+impl std::fmt::Display for ExtractorError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    std::fmt::Display::fmt(self.inner(), f)
+  }
+}
+
+impl std::error::Error for ExtractorError {
+  fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+    match self {
+      Self::Json(err) => err.source(),
+      Self::Query(err) => err.source(),
+      Self::Path(err) => err.source(),
+      Self::Form(err) => err.source(),
+    }
+  }
+}
+
+crate::__submit_registered_error!(ExtractorError, "ExtractorError", ExtractorError::variant);
+
+impl ThiserrorResponse for ExtractorError {
+  fn response_meta(&self) -> ResponseMeta {
+    let (status_code, reason) = self.response();
+
+    ResponseMeta::new().status_code(status_code).reason(reason)
+  }
+}
+
+impl actix_web::ResponseError for ExtractorError {
+  fn status_code(&self) -> StatusCode {
+    self.response().0
+  }
+
+  fn error_response(&self) -> HttpResponse {
+    let mut context = ErrorContext::new("ExtractorError", self, self.status_code());
+    context.variant = self.variant();
+    context.set_meta(self.response_meta());
+
+    crate::respond(&context, self)
+  }
+}
+
+/// The error handler of [`JsonConfig`][web::JsonConfig].
+pub fn json_error(err: JsonPayloadError, _req: &HttpRequest) -> actix_web::Error {
+  ExtractorError::Json(err).into()
+}
+
+/// The error handler of [`QueryConfig`][web::QueryConfig].
+pub fn query_error(err: QueryPayloadError, _req: &HttpRequest) -> actix_web::Error {
+  ExtractorError::Query(err).into()
+}
+
+/// The error handler of [`PathConfig`][web::PathConfig].
+pub fn path_error(err: PathError, _req: &HttpRequest) -> actix_web::Error {
+  ExtractorError::Path(err).into()
+}
+
+/// The error handler of [`FormConfig`][web::FormConfig].
+pub fn form_error(err: UrlencodedError, _req: &HttpRequest) -> actix_web::Error {
+  ExtractorError::Form(err).into()
+}
+
+/// Installs the error handlers of the `Json`, `Query`, `Path` and `Form`
+/// extractors, with their default configurations otherwise.
+///
+/// To keep a configuration of your own, such as a larger JSON limit, set the
+/// matching handler on it instead, e.g.
+/// `JsonConfig::default().limit(1 << 20).error_handler(extractors::json_error)`.
+pub fn configure(cfg: &mut web::ServiceConfig) {
+  cfg
+    .app_data(web::JsonConfig::default().error_handler(json_error))
+    .app_data(web::QueryConfig::default().error_handler(query_error))
+    .app_data(web::PathConfig::default().error_handler(path_error))
+    .app_data(web::FormConfig::default().error_handler(form_error));
+}
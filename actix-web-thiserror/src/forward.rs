@@ -0,0 +1,123 @@
+//! Forwarding through fields that may not implement [`ThiserrorResponse`],
+//! for `#[response(auto_forward)]`.
+//!
+//! `(&Forward(&inner)).response_meta()` resolves to [`ForwardResponse`] when the
+//! type of `inner` implements [`ThiserrorResponse`], and to
+//! [`ForwardFallback`], which leaves everything unset, otherwise.
+
+use crate::{ErrorResponseParts, FromErrorResponse, ResponseMeta, ThiserrorResponse};
+
+pub struct Forward<'a, T>(pub &'a T);
+
+pub trait ForwardResponse {
+  fn response_meta(&self) -> ResponseMeta;
+
+  fn redacted_display(&self) -> Option<String>;
+
+  fn redacted_debug(&self) -> Option<String>;
+}
+
+impl<T: ThiserrorResponse> ForwardResponse for Forward<'_, T> {
+  fn response_meta(&self) -> ResponseMeta {
+    self.0.response_meta()
+  }
+
+  fn redacted_display(&self) -> Option<String> {
+    self.0.redacted_display()
+  }
+
+  fn redacted_debug(&self) -> Option<String> {
+    self.0.redacted_debug()
+  }
+}
+
+pub trait ForwardFallback {
+  fn response_meta(&self) -> ResponseMeta {
+    ResponseMeta::new()
+  }
+
+  fn redacted_display(&self) -> Option<String> {
+    None
+  }
+
+  fn redacted_debug(&self) -> Option<String> {
+    None
+  }
+}
+
+impl<T> ForwardFallback for &Forward<'_, T> {}
+
+/// Whether a forwarded type implements [`ThiserrorResponse`], through
+/// `(&ForwardType::<T>(PhantomData)).forwards()`. Variants whose field does not
+/// are treated as any other variant.
+pub struct ForwardType<T>(pub std::marker::PhantomData<T>);
+
+pub trait ForwardTypeResponse {
+  fn forwards(&self) -> bool {
+    true
+  }
+}
+
+impl<T: ThiserrorResponse> ForwardTypeResponse for ForwardType<T> {}
+
+pub trait ForwardTypeFallback {
+  fn forwards(&self) -> bool {
+    false
+  }
+}
+
+impl<T> ForwardTypeFallback for &ForwardType<T> {}
+
+/// Reads a forwarded error back from a response, when the type of the field
+/// implements [`FromErrorResponse`], for `#[derive(FromErrorResponse)]`.
+pub struct ForwardParts<T>(pub std::marker::PhantomData<T>);
+
+pub trait ForwardFromParts<T> {
+  fn read_parts(&self, parts: &ErrorResponseParts) -> Option<T>;
+}
+
+impl<T: FromErrorResponse> ForwardFromParts<T> for ForwardParts<T> {
+  fn read_parts(&self, parts: &ErrorResponseParts) -> Option<T> {
+    T::from_parts(parts)
+  }
+}
+
+pub trait ForwardFromPartsFallback<T> {
+  #[allow(unused_variables)]
+  fn read_parts(&self, parts: &ErrorResponseParts) -> Option<T> {
+    None
+  }
+}
+
+impl<T> ForwardFromPartsFallback<T> for &ForwardParts<T> {}
+
+/// The responses documented for a forwarded type, when it implements
+/// `utoipa::IntoResponses`.
+#[cfg(feature = "openapi")]
+pub struct ForwardResponses<T>(pub std::marker::PhantomData<T>);
+
+#[cfg(feature = "openapi")]
+type Responses =
+  std::collections::BTreeMap<String, utoipa::openapi::RefOr<utoipa::openapi::Response>>;
+
+#[cfg(feature = "openapi")]
+pub trait ForwardIntoResponses {
+  fn responses(&self) -> Responses;
+}
+
+#[cfg(feature = "openapi")]
+impl<T: utoipa::IntoResponses> ForwardIntoResponses for ForwardResponses<T> {
+  fn responses(&self) -> Responses {
+    T::responses()
+  }
+}
+
+#[cfg(feature = "openapi")]
+pub trait ForwardIntoResponsesFallback {
+  fn responses(&self) -> Responses {
+    Responses::new()
+  }
+}
+
+#[cfg(feature = "openapi")]
+impl<T> ForwardIntoResponsesFallback for &ForwardResponses<T> {}
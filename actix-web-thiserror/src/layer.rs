@@ -0,0 +1,164 @@
+use actix_web::HttpResponse;
+
+#[cfg(feature = "openapi")]
+use crate::ErrorCatalogEntry;
+use crate::{ErrorContext, ResponseTransform};
+
+/// A step applied to the response of a [`ResponseTransform`], with access to
+/// the context of the error.
+///
+/// Closures taking the context and the response implement this trait.
+pub trait TransformLayer {
+  fn layer(&self, context: &ErrorContext, response: HttpResponse) -> HttpResponse;
+}
+
+impl<F> TransformLayer for F
+where
+  F: Fn(&ErrorContext, HttpResponse) -> HttpResponse,
+{
+  fn layer(&self, context: &ErrorContext, response: HttpResponse) -> HttpResponse {
+    self(context, response)
+  }
+}
+
+/// Combinators layering behavior over a [`ResponseTransform`].
+///
+/// ```rust
+/// # use actix_web::http::header::HeaderValue;
+/// # use actix_web::HttpResponse;
+/// # use actix_web_thiserror::{ErrorContext, ResponseTransform, ResponseTransformExt as _, REQUEST_ID_HEADER};
+/// # struct ProblemTransform;
+/// # impl ResponseTransform for ProblemTransform {}
+/// let transform = ProblemTransform
+///   // Keep the details of server errors private.
+///   .map_context(|context| {
+///     if context.status_code.is_server_error() {
+///       context.details = None;
+///     }
+///   })
+///   // Echo the correlation id.
+///   .then(|context: &ErrorContext, mut response: HttpResponse| {
+///     if let Ok(value) = HeaderValue::from_str(&context.correlation_id) {
+///       response.headers_mut().insert(REQUEST_ID_HEADER, value);
+///     }
+///
+///     response
+///   });
+///
+/// actix_web_thiserror::set_global_transform(transform);
+/// ```
+pub trait ResponseTransformExt: ResponseTransform + Sized {
+  /// Passes the response of this transform through `layer`.
+  fn then<L: TransformLayer>(self, layer: L) -> Then<Self, L> {
+    Then {
+      transform: self,
+      layer,
+    }
+  }
+
+  /// Maps the response of this transform with `f`.
+  fn map_response<F: Fn(HttpResponse) -> HttpResponse>(self, f: F) -> MapResponse<Self, F> {
+    MapResponse { transform: self, f }
+  }
+
+  /// Modifies a copy of the context with `f` before handing it to this
+  /// transform.
+  fn map_context<F: Fn(&mut ErrorContext)>(self, f: F) -> MapContext<Self, F> {
+    MapContext { transform: self, f }
+  }
+}
+
+impl<T: ResponseTransform> ResponseTransformExt for T {}
+
+/// Delegates everything but `transform_with_context` to the wrapped transform,
+/// and `transform` to `transform_with_context`.
+macro_rules! delegate_transform {
+  () => {
+    fn transform(
+      &self,
+      name: &str,
+      err: &dyn std::error::Error,
+      status_code: actix_web::http::StatusCode,
+      reason: Option<serde_json::Value>,
+      _type: Option<String>,
+      details: Option<serde_json::Value>,
+    ) -> HttpResponse {
+      self.transform_with_context(&ErrorContext::from_parts(
+        name,
+        err,
+        status_code,
+        reason,
+        _type,
+        details,
+      ))
+    }
+
+    fn default_error_status_code(&self) -> actix_web::http::StatusCode {
+      self.transform.default_error_status_code()
+    }
+
+    #[cfg(feature = "openapi")]
+    fn openapi_schema(&self) -> Option<utoipa::openapi::RefOr<utoipa::openapi::schema::Schema>> {
+      self.transform.openapi_schema()
+    }
+
+    #[cfg(feature = "openapi")]
+    fn openapi_example(&self, entry: &ErrorCatalogEntry) -> Option<serde_json::Value> {
+      self.transform.openapi_example(entry)
+    }
+
+    #[cfg(feature = "openapi")]
+    fn openapi_content_type(&self) -> &str {
+      self.transform.openapi_content_type()
+    }
+  };
+}
+
+/// The transform returned by [`ResponseTransformExt::then`].
+pub struct Then<T, L> {
+  transform: T,
+  layer: L,
+}
+
+impl<T: ResponseTransform, L: TransformLayer> ResponseTransform for Then<T, L> {
+  fn transform_with_context(&self, context: &ErrorContext) -> HttpResponse {
+    self
+      .layer
+      .layer(context, self.transform.transform_with_context(context))
+  }
+
+  delegate_transform!();
+}
+
+/// The transform returned by [`ResponseTransformExt::map_response`].
+pub struct MapResponse<T, F> {
+  transform: T,
+  f: F,
+}
+
+impl<T: ResponseTransform, F: Fn(HttpResponse) -> HttpResponse> ResponseTransform
+  for MapResponse<T, F>
+{
+  fn transform_with_context(&self, context: &ErrorContext) -> HttpResponse {
+    (self.f)(self.transform.transform_with_context(context))
+  }
+
+  delegate_transform!();
+}
+
+/// The transform returned by [`ResponseTransformExt::map_context`].
+pub struct MapContext<T, F> {
+  transform: T,
+  f: F,
+}
+
+impl<T: ResponseTransform, F: Fn(&mut ErrorContext)> ResponseTransform for MapContext<T, F> {
+  fn transform_with_context(&self, context: &ErrorContext) -> HttpResponse {
+    let mut context = context.to_owned();
+    (self.f)(&mut context);
+
+    self.transform.transform_with_context(&context)
+  }
+
+  delegate_transform!();
+}
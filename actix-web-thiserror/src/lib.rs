@@ -28,6 +28,130 @@
 //! }
 //! ```
 //!
+//! ## Reasons from variant names
+//!
+//! Instead of writing a `reason` on every variant, it can be derived from the
+//! variant name. Variants with an explicit `reason` or `forward` are left alone.
+//!
+//! ```rust
+//! # use actix_web_thiserror::ResponseError;
+//! # use thiserror::Error;
+//! #
+//! #[derive(Debug, Error, ResponseError)]
+//! #[response(reason_from_variant = "SCREAMING_SNAKE_CASE", reason_prefix = "IMG_")]
+//! pub enum Base64ImageError {
+//!   // reason: "IMG_INVALID_IMAGE_FORMAT"
+//!   #[error("invalid image format")]
+//!   InvalidImageFormat,
+//!   // reason: "INVALID_STRING"
+//!   #[response(reason = "INVALID_STRING")]
+//!   #[error("invalid string")]
+//!   InvalidString,
+//! }
+//! ```
+//!
+//! The supported cases are `lowercase`, `UPPERCASE`, `PascalCase`, `camelCase`,
+//! `snake_case`, `SCREAMING_SNAKE_CASE`, `kebab-case` and `SCREAMING-KEBAB-CASE`.
+//!
+//! ## Unique reasons
+//!
+//! Clients often match on the `reason`, so two variants sharing one is usually a
+//! mistake. Adding `#[response(unique_reasons)]` to the enum turns duplicate literal
+//! reasons, including ones from `reason_from_variant`, into a compile error.
+//!
+//! ```rust,compile_fail
+//! # use actix_web_thiserror::ResponseError;
+//! # use thiserror::Error;
+//! #
+//! #[derive(Debug, Error, ResponseError)]
+//! #[response(unique_reasons)]
+//! pub enum LookupError {
+//!   #[response(reason = "NOT_FOUND")]
+//!   #[error("user not found")]
+//!   UserNotFound,
+//!   // error: duplicate reason "NOT_FOUND" in #[response(unique_reasons)]
+//!   #[response(reason = "NOT_FOUND")]
+//!   #[error("image not found")]
+//!   ImageNotFound,
+//! }
+//! ```
+//!
+//! ## Forwarding wrapped errors
+//!
+//! A variant marked `#[response(forward)]` takes its status, reason, type and
+//! details from the error it wraps. With `#[response(auto_forward)]` on the enum,
+//! variants are forwarded through the field thiserror already knows about: the
+//! field of an `#[error(transparent)]` variant, or its `#[from]`, `#[source]` or
+//! `source` field. Options written on a variant still take precedence, and variants
+//! wrapping errors that do not derive `ResponseError` are not forwarded: they are
+//! listed, documented and given a reason like any other. Fields whose type is
+//! a generic parameter are forwarded like `#[response(forward)]` ones, so the
+//! implementations are bounded on the parameter implementing `ThiserrorResponse`.
+//!
+//! ```rust
+//! # use actix_web_thiserror::ResponseError;
+//! # use thiserror::Error;
+//! #
+//! # #[derive(Debug, Error, ResponseError)]
+//! # pub enum Base64ImageError {
+//! #   #[response(reason = "INVALID_IMAGE_FORMAT")]
+//! #   #[error("invalid image format")]
+//! #   InvalidImageFormat,
+//! # }
+//! #
+//! #[derive(Debug, Error, ResponseError)]
+//! #[response(auto_forward)]
+//! pub enum UploadError {
+//!   // status and reason of the image error
+//!   #[error(transparent)]
+//!   Image(#[from] Base64ImageError),
+//!   // reason of the image error, with a status of 400
+//!   #[response(status = 400)]
+//!   #[error("invalid thumbnail")]
+//!   Thumbnail(#[source] Base64ImageError),
+//!   // status of 500
+//!   #[error("could not store upload")]
+//!   Io(#[from] std::io::Error),
+//! }
+//! ```
+//!
+//! ## Manual implementations
+//!
+//! Forwarding reads the [`ThiserrorResponse`][thiserror_response] trait, which the
+//! derive implements and which can be implemented by hand for errors it does not
+//! cover. It returns a `ResponseMeta` where every value starts out unset: unset
+//! values come from the error being forwarded to, or fall back to the defaults,
+//! while `MetaValue::Omitted` leaves a value out even when the forwarded error has
+//! one.
+//!
+//! ```rust
+//! use actix_web::http::StatusCode;
+//! use actix_web_thiserror::{ResponseError, ResponseMeta, ThiserrorResponse};
+//! use thiserror::Error;
+//!
+//! #[derive(Debug, Error)]
+//! #[error(transparent)]
+//! pub struct DatabaseError(std::io::Error);
+//!
+//! impl ThiserrorResponse for DatabaseError {
+//!   fn response_meta(&self) -> ResponseMeta {
+//!     match self.0.kind() {
+//!       std::io::ErrorKind::NotFound => ResponseMeta::new()
+//!         .status_code(StatusCode::NOT_FOUND)
+//!         .reason("NOT_FOUND"),
+//!       _ => ResponseMeta::new().reason("DATABASE_ERROR"),
+//!     }
+//!   }
+//! }
+//!
+//! #[derive(Debug, Error, ResponseError)]
+//! pub enum UserError {
+//!   #[response(forward)]
+//!   #[error(transparent)]
+//!   Database(DatabaseError),
+//! }
+//! ```
+//!
 //! ## Error implementation
 //! ```rust
 //! # use actix_web_thiserror::ResponseError;
@@ -46,12 +170,82 @@
 //! }
 //! ```
 //!
+//! ## Ad-hoc errors
+//!
+//! For a one-off response that does not deserve a variant, `ApiError` is built at
+//! runtime. It goes through the same transform, logging and observers as derived
+//! errors, and derived enums can forward to it.
+//!
+//! ```rust
+//! # use actix_web::http::StatusCode;
+//! # use actix_web::HttpResponse;
+//! # use actix_web_thiserror::ApiError;
+//! # use serde_json::json;
+//! #
+//! pub async fn update() -> Result<HttpResponse, ApiError> {
+//!   Err(
+//!     ApiError::new(StatusCode::CONFLICT)
+//!       .reason("VERSION_MISMATCH")
+//!       .details(json!({ "expected": 3, "found": 2 })),
+//!   )
+//! }
+//! ```
+//!
+//! ## Type-erased errors
+//!
+//! With the `anyhow` feature enabled, handlers returning `anyhow::Result` can keep
+//! the responses of their errors by converting into `AnyResponse`. It responds
+//! like the outermost error of the chain that derives `ResponseError`, or that was
+//! registered with `register_response!`, and with the default status code of the
+//! transform otherwise. Generic enums are not registered.
+//!
+//! ```rust,ignore
+//! use actix_web_thiserror::AnyResponse;
+//! use anyhow::Context as _;
+//!
+//! pub async fn profile() -> Result<HttpResponse, AnyResponse> {
+//!   let profile = load_profile().context("loading the profile")?;
+//!
+//!   Ok(HttpResponse::Ok().json(profile))
+//! }
+//! ```
+//!
+//! The registry behind it is available on its own with the `registry` feature,
+//! where `registry::find_registered` searches the source chain of any error, such
+//! as a `Box<dyn Error>`.
+//!
+//! Once an error has been converted into an `actix_web::Error`, middleware can get
+//! it back with `RegisteredError::from_actix_error`, along with its variant,
+//! status code and response metadata.
+//!
+//! ```rust,ignore
+//! App::new().wrap_fn(|req, srv| {
+//!   let res = srv.call(req);
+//!
+//!   async move {
+//!     let res = res.await?;
+//!
+//!     if let Some(registered) = res.response().error().and_then(RegisteredError::from_actix_error) {
+//!       log::info!("{}::{} -> {}", registered.name, registered.variant, registered.status_code);
+//!     }
+//!
+//!     Ok(res)
+//!   }
+//! })
+//! ```
+//!
 //! ## Error response
 //!
 //! The `reason` is a string that may be given to the client in some form to explain
 //! the error, if appropriate. Here it is as an enum that can be localized.
 //!
 //! **Note:** This response has been formatted by a [`ResponseTransform`][response_transform].
+//! To render an enum with another transform than the global one, add
+//! `#[response(transform = path::to::MyTransform)]` under your derive, naming a
+//! unit struct, a constant or any expression implementing
+//! [`ResponseTransform`][response_transform]. The option can also be set on a
+//! single variant, and `#[response(transform = custom)]` uses the error type
+//! itself as its transform.
 //!
 //! ```json
 //! {
@@ -60,19 +254,324 @@
 //! }
 //! ```
 //!
+//! This body is produced by the built-in `LegacyResultTransform`, installed with
+//! `set_global_transform(LegacyResultTransform::new())`. Its key names can be
+//! changed, and the `type`, `details` and exposed message of errors added under
+//! keys of your choosing. `JsonTransform` renders the same shape with every key the
+//! error has. Transforms that only change the body can implement `BodyTransform`,
+//! returning anything `Serialize`, and leave building the JSON response to the
+//! crate.
+//!
+//! The global transform is set with `set_global_transform`. Tests that need a
+//! different one can override it on the current thread with a `TransformGuard`, or
+//! `with_transform(transform, || ...)`, without racing other tests.
+//!
+//! Transforms compose through `ResponseTransformExt`: `map_context` adjusts the
+//! `ErrorContext` before a transform sees it, `then` passes the response on to a
+//! `TransformLayer` along with the context, and `map_response` maps the response.
+//!
+//! ## Extractor errors
+//!
+//! Malformed JSON bodies, query strings, paths and forms are answered by actix with
+//! plain-text responses of its own. `extractors::configure` installs error
+//! handlers rendering them through the transform instead, with reasons such as
+//! `INVALID_JSON`, `INVALID_QUERY` or `PAYLOAD_TOO_LARGE`.
+//!
+//! ```rust
+//! # use actix_web::{web, App};
+//! #
+//! # let _ =
+//! App::new()
+//!   .configure(actix_web_thiserror::extractors::configure)
+//!   // or, to keep a configuration of your own:
+//!   .app_data(
+//!     web::JsonConfig::default()
+//!       .limit(1 << 20)
+//!       .error_handler(actix_web_thiserror::extractors::json_error),
+//!   )
+//! # ;
+//! ```
+//!
+//! ## Unmatched routes and foreign errors
+//!
+//! Errors that do not come from this crate, such as the 404 of unmatched routes,
+//! the 405 of unmatched methods or the `actix_web::Error`s of other crates, keep
+//! the responses of actix. The `ErrorNormalizer` middleware renders them through
+//! the transform as a `NormalizedError`, with a reason derived from the status
+//! code, such as `NOT_FOUND` or `METHOD_NOT_ALLOWED`. Error responses with a body
+//! of their own are left untouched.
+//!
+//! ```rust
+//! # use actix_web::App;
+//! # use actix_web_thiserror::ErrorNormalizer;
+//! #
+//! # let _ =
+//! App::new()
+//!   .wrap(ErrorNormalizer)
+//!   // or, for unmatched routes only:
+//!   .default_service(actix_web_thiserror::default_service())
+//! # ;
+//! ```
+//!
+//! ## Panics
+//!
+//! A panic in a handler drops the connection without a response. The `CatchPanic`
+//! middleware catches unwinding panics instead, which are logged, observed and
+//! rendered through the transform as a `PanicError` with a 500 and the `PANIC`
+//! reason.
+//!
+//! ```rust
+//! # use actix_web::App;
+//! # use actix_web_thiserror::CatchPanic;
+//! #
+//! # let _ =
+//! App::new().wrap(CatchPanic::new().reason("INTERNAL_PANIC"))
+//! # ;
+//! ```
+//!
+//! ## Message exposure
+//!
+//! Error messages often contain details that should not reach clients, such as
+//! SQL fragments. Messages are private unless a variant is marked with
+//! `#[response(public = "...")]` or `#[response(expose)]`, and the built-in
+//! transforms only render private messages, along with their source chain, once
+//! `set_exposure(Exposure::Development)` is called. Custom transforms get the same
+//! behavior from `ErrorContext::message` by implementing
+//! `ResponseTransform::transform_with_context`.
+//!
+//! ```rust
+//! # use actix_web_thiserror::ResponseError;
+//! # use thiserror::Error;
+//! #
+//! #[derive(Debug, Error, ResponseError)]
+//! pub enum UserError {
+//!   // Never shown to clients.
+//!   #[error("query failed: {0}")]
+//!   Query(String),
+//!   // Shown as "the user is invalid".
+//!   #[response(public = "the user is invalid")]
+//!   #[error("invalid user {0}")]
+//!   InvalidUser(u32),
+//!   // Shown as "user 1 not found".
+//!   #[response(expose)]
+//!   #[error("user {0} not found")]
+//!   NotFound(u32),
+//! }
+//! ```
+//!
+//! ## Sensitive fields
+//!
+//! Fields marked with `#[response(sensitive)]` are replaced with `[REDACTED]` in
+//! the logged error, in `details` and in exposed messages, so secrets and personal
+//! data never reach logs or clients. Messages passing extra format arguments are
+//! redacted as a whole when the variant has sensitive fields. Errors forwarding to
+//! one with sensitive fields are masked the same way when they are logged.
+//!
+//! ```rust
+//! # use actix_web_thiserror::ResponseError;
+//! # use thiserror::Error;
+//! #
+//! #[derive(Debug, Error, ResponseError)]
+//! pub enum LoginError {
+//!   // Logged as `InvalidLogin { email: "a@b.c", password_hint: [REDACTED] }`.
+//!   #[response(status = 401, expose)]
+//!   #[error("invalid login for {email}")]
+//!   InvalidLogin {
+//!     email: String,
+//!     #[response(sensitive)]
+//!     password_hint: String,
+//!   },
+//! }
+//! ```
+//!
+//! ## Localization
+//!
+//! With the `localization` feature enabled, `LocalizingTransform` treats the
+//! `reason` as a message key and looks it up in Fluent (`.ftl`) or JSON catalogs,
+//! picking the locale from the `Accept-Language` header captured by the
+//! `Localization` middleware. Fields passed through `details` are available to
+//! the messages, and the `Display` text is used when a key is missing.
+//!
+//! ```rust,ignore
+//! actix_web_thiserror::set_global_transform(
+//!   LocalizingTransform::new("en-US").with_dir("locales")?,
+//! );
+//!
+//! HttpServer::new(|| App::new().wrap(Localization))
+//! ```
+//!
+//! ## Error catalog
+//!
+//! Every enum deriving `ResponseError` also implements `ThiserrorCatalog`, which
+//! describes each variant's status, reason, type, message and details. With the
+//! `catalog` feature enabled, the catalogs of every such enum linked into the
+//! binary can be listed at once, e.g. to document them at startup or in a test.
+//!
+//! ```rust
+//! # use actix_web_thiserror::ResponseError;
+//! # use thiserror::Error;
+//! #
+//! # #[derive(Debug, Error, ResponseError)]
+//! # pub enum Base64ImageError {
+//! #   #[response(reason = "INVALID_IMAGE_FORMAT")]
+//! #   #[error("invalid image format")]
+//! #   InvalidImageFormat,
+//! # }
+//! #
+//! use actix_web_thiserror::ThiserrorCatalog;
+//!
+//! let catalog = Base64ImageError::error_catalog();
+//! println!("{}", catalog.to_markdown());
+//!
+//! #[cfg(feature = "catalog")]
+//! for catalog in actix_web_thiserror::error_catalogs() {
+//!   println!("{}", catalog.to_json());
+//! }
+//! ```
+//!
+//! ## OpenAPI
+//!
+//! With the `openapi` feature enabled, the derive also implements
+//! [`utoipa::IntoResponses`][into_responses], grouping variants by status code and
+//! following forwarded errors. The schema and examples of each response come from
+//! the global `ResponseTransform`, through its `openapi_schema` and
+//! `openapi_example` methods, which a `BodyTransform` implements the same way.
+//! Enums and variants with `#[response(transform = ...)]` are documented with
+//! their own transform, and grouped under its default status code.
+//!
+//! ```rust,ignore
+//! #[utoipa::path(get, path = "/image", responses(Base64ImageError))]
+//! pub async fn image() -> Result<HttpResponse, Error> {
+//!   Err(Base64ImageError::InvalidImageFormat)?
+//! }
+//! ```
+//!
+//! ## Client-side errors
+//!
+//! Services calling each other can get the typed error back from a response.
+//! `#[derive(FromErrorResponse)]`, next to `ResponseError`, reconstructs a variant
+//! from the status code and the JSON body of a built-in transform by matching its
+//! `reason`, and its `status` when it has one. Unit variants, variants whose only
+//! field is sent as `details = "{0}"` and is `Deserialize`, and forwarded variants
+//! can be reconstructed.
+//!
+//! ```rust
+//! # use actix_web::http::StatusCode;
+//! # use actix_web_thiserror::{FromErrorResponse, ResponseError};
+//! # use thiserror::Error;
+//! #
+//! #[derive(Debug, Error, FromErrorResponse, ResponseError)]
+//! pub enum Base64ImageError {
+//!   #[response(status = 400, reason = "INVALID_IMAGE_FORMAT")]
+//!   #[error("invalid image format")]
+//!   InvalidImageFormat,
+//! }
+//!
+//! let err = Base64ImageError::from_error_response(
+//!   StatusCode::BAD_REQUEST,
+//!   br#"{"result": 0, "reason": "INVALID_IMAGE_FORMAT"}"#,
+//! );
+//! # assert!(matches!(err, Some(Base64ImageError::InvalidImageFormat)));
+//! ```
+//!
+//! ## Error observers
+//!
+//! Every error turned into a response is handed to the observers registered with
+//! `register_error_observer`, after the transform built the response, so it can be
+//! forwarded to metrics, tracing or error tracking without wrapping the transform.
+//! Observers receive the same `ErrorContext`, along with the name of the variant.
+//! They run in registration order, and one that panics is skipped without
+//! affecting the others or the response.
+//!
+//! With the `metrics` feature enabled, each error also increments the
+//! `actix_web_thiserror_errors_total` counter of the [metrics] facade, labeled by
+//! `error`, `variant`, `status` and `reason`.
+//!
+//! ```rust
+//! # use actix_web_thiserror::{ErrorContext, ErrorObserver};
+//! #
+//! struct VariantLogger;
+//!
+//! impl ErrorObserver for VariantLogger {
+//!   fn observe(&self, context: &ErrorContext) {
+//!     println!("{}::{} -> {}", context.name, context.variant, context.status_code);
+//!   }
+//! }
+//!
+//! actix_web_thiserror::register_error_observer(VariantLogger);
+//! ```
+//!
 //! ## Error logging
 //!
 //! The error text automatically prints to the log when the error is returned out
 //! through a http response.
 //!
 //! ```text
-//! Apr 23 02:19:35.211 ERROR Response error: invalid image format
+//! Apr 23 02:19:35.211 ERROR Response error [5f0c2a9e41d7b3c8a6e1f09d2b7c4e13]: invalid image format
 //!     Base64ImageError(InvalidImageFormat), place: example/src/handler.rs:5 example::handler
 //! ```
 //!
+//! Each record carries a correlation id. Wrap the app in the `RequestId`
+//! middleware to take it from the `X-Request-Id` header of the request, or
+//! generate one when it is missing. Transforms receive it as
+//! `ErrorContext::correlation_id`, so they can echo it in the body or headers and
+//! tie what the client sees to the log line.
+//!
+//! ```rust
+//! # use actix_web::{App, HttpResponse};
+//! # use actix_web_thiserror::{ErrorContext, RequestId, ResponseError, ResponseTransform, REQUEST_ID_HEADER};
+//! # use serde_json::json;
+//! # use thiserror::Error;
+//! #
+//! #[derive(Debug, Error, ResponseError)]
+//! pub enum UploadError {
+//!   #[response(status = 413)]
+//!   #[error("file too large")]
+//!   TooLarge,
+//! }
+//!
+//! struct CorrelatedTransform;
+//!
+//! impl ResponseTransform for CorrelatedTransform {
+//!   fn transform_with_context(&self, context: &ErrorContext) -> HttpResponse {
+//!     HttpResponse::build(context.status_code)
+//!       .insert_header((REQUEST_ID_HEADER, context.correlation_id.to_owned()))
+//!       .json(json!({ "reason": context.reason, "request_id": context.correlation_id }))
+//!   }
+//! }
+//!
+//! # let _ =
+//! App::new().wrap(RequestId)
+//! # ;
+//! ```
+//!
+//! ## Testing
+//!
+//! With the `test-util` feature enabled, `assert_response!` checks the status and
+//! body an error renders with the transform in effect, and the reason, type,
+//! details and public message it was rendered with, whatever the shape of the
+//! body. `test_util::render` returns the status and JSON body for assertions of
+//! your own, and `test_util::RecordingTransform` records every error it renders.
+//!
+//! ```rust,ignore
+//! #[test]
+//! fn user_not_found() {
+//!   assert_response!(UserError::NotFound, status = 404, reason = "USER_NOT_FOUND");
+//!
+//!   let (status, body) = with_transform(LegacyResultTransform::new(), || {
+//!     test_util::render(&UserError::NotFound)
+//!   });
+//!   assert_eq!(status, 404);
+//!   assert_eq!(body, json!({ "result": 0, "reason": "USER_NOT_FOUND" }));
+//! }
+//! ```
+//!
 //! [thiserror]: https://docs.rs/thiserror
 //! [actix-web]: https://docs.rs/actix-web
 //! [response_transform]: crate::ResponseTransform
+//! [thiserror_response]: crate::ThiserrorResponse
+//! [into_responses]: https://docs.rs/utoipa/latest/utoipa/trait.IntoResponses.html
+//! [metrics]: https://docs.rs/metrics
 
 use std::sync::Arc;
 
@@ -80,6 +579,111 @@ use actix_web::HttpResponse;
 use arc_swap::ArcSwap;
 use lazy_static::lazy_static;
 
+#[cfg(feature = "anyhow")]
+mod any_response;
+mod api_error;
+mod body;
+mod catalog;
+mod client;
+mod context;
+mod correlation;
+mod exposure;
+pub mod extractors;
+mod forward;
+mod layer;
+#[cfg(feature = "localization")]
+pub mod localization;
+mod meta;
+#[cfg(feature = "metrics")]
+pub mod metrics;
+mod normalizer;
+mod observer;
+#[cfg(feature = "openapi")]
+pub mod openapi;
+mod panic;
+#[cfg(feature = "registry")]
+pub mod registry;
+mod scoped;
+#[cfg(feature = "test-util")]
+pub mod test_util;
+
+#[cfg(feature = "anyhow")]
+pub use any_response::AnyResponse;
+pub use api_error::ApiError;
+pub use body::{BodyTransform, JsonBody, JsonTransform, LegacyResultTransform};
+#[cfg(feature = "catalog")]
+pub use catalog::error_catalogs;
+#[doc(hidden)]
+#[cfg(feature = "catalog")]
+pub use catalog::ErrorCatalogRegistration;
+pub use catalog::{ErrorCatalog, ErrorCatalogEntry, ThiserrorCatalog};
+pub use client::{ErrorResponseParts, FromErrorResponse};
+pub use context::ErrorContext;
+#[doc(hidden)]
+pub use correlation::RequestIdMiddleware;
+pub use correlation::{correlation_id, RequestId, REQUEST_ID_HEADER};
+pub use exposure::{exposure, set_exposure, Exposure};
+pub use layer::{MapContext, MapResponse, ResponseTransformExt, Then, TransformLayer};
+pub use meta::{MetaValue, ResponseMeta, ThiserrorResponse};
+#[doc(hidden)]
+pub use normalizer::ErrorNormalizerMiddleware;
+pub use normalizer::{default_service, ErrorNormalizer, NormalizedError};
+#[doc(hidden)]
+pub use observer::observe_error;
+pub use observer::{register_error_observer, ErrorObserver};
+#[doc(hidden)]
+pub use panic::CatchPanicMiddleware;
+pub use panic::{CatchPanic, PanicError};
+pub use scoped::{with_transform, TransformGuard};
+
+#[doc(hidden)]
+pub mod __private {
+  #[cfg(any(feature = "catalog", feature = "registry"))]
+  pub use inventory;
+  #[cfg(feature = "openapi")]
+  pub use utoipa;
+
+  pub use crate::forward::{
+    Forward, ForwardFallback, ForwardFromParts, ForwardFromPartsFallback, ForwardParts,
+    ForwardResponse, ForwardType, ForwardTypeFallback, ForwardTypeResponse,
+  };
+  #[cfg(feature = "openapi")]
+  pub use crate::forward::{ForwardIntoResponses, ForwardIntoResponsesFallback, ForwardResponses};
+  pub use crate::normalizer::mark_rendered;
+
+  /// Formats through a closure, used to log errors with sensitive fields.
+  pub struct DebugFn<'a>(pub &'a dyn Fn(&mut std::fmt::Formatter<'_>) -> std::fmt::Result);
+
+  impl std::fmt::Debug for DebugFn<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+      (self.0)(f)
+    }
+  }
+}
+
+#[cfg(feature = "openapi")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __cfg_openapi {
+  ($($item:tt)*) => {
+    $($item)*
+  };
+}
+
+#[cfg(not(feature = "openapi"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __cfg_openapi {
+  ($($item:tt)*) => {};
+}
+
+#[cfg(not(feature = "registry"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __submit_registered_error {
+  ($ty:ty, $name:expr, $variant:expr) => {};
+}
+
 /// A trait that transforms information about an [thiserror] error into
 /// a response as desired by the implementor.
 ///
@@ -98,9 +702,44 @@ pub trait ResponseTransform {
     actix_web::HttpResponse::build(status_code).finish()
   }
 
+  /// Transforms an error using everything known about it.
+  ///
+  /// This is what the derive calls. By default it hands the context over to
+  /// [`transform`][ResponseTransform::transform].
+  fn transform_with_context(&self, context: &ErrorContext) -> HttpResponse {
+    self.transform(
+      context.name,
+      context.err,
+      context.status_code,
+      context.reason.to_owned(),
+      context._type.to_owned(),
+      context.details.to_owned(),
+    )
+  }
+
   fn default_error_status_code(&self) -> actix_web::http::StatusCode {
     actix_web::http::StatusCode::INTERNAL_SERVER_ERROR
   }
+
+  /// The schema of the bodies this transform produces, documented for every
+  /// error response by the `openapi` feature.
+  #[cfg(feature = "openapi")]
+  fn openapi_schema(&self) -> Option<utoipa::openapi::RefOr<utoipa::openapi::schema::Schema>> {
+    None
+  }
+
+  /// The body this transform would produce for a variant, used as its example
+  /// by the `openapi` feature.
+  #[cfg(feature = "openapi")]
+  fn openapi_example(&self, entry: &ErrorCatalogEntry) -> Option<serde_json::Value> {
+    None
+  }
+
+  /// The content type of the bodies this transform produces.
+  #[cfg(feature = "openapi")]
+  fn openapi_content_type(&self) -> &str {
+    "application/json"
+  }
 }
 
 struct ReflexiveTransform;
@@ -113,10 +752,21 @@ lazy_static! {
 }
 
 /// Sets the default global transform for errors into responses.
+///
+/// This is overridden on threads holding a [`TransformGuard`].
 pub fn set_global_transform(transform: impl ResponseTransform + Sync + Send + 'static) {
   RESPONSE_TRANSFORM.swap(Arc::new(Box::new(transform)));
 }
 
+/// Calls `f` with the transform in effect on the current thread: the innermost
+/// [`TransformGuard`], or the global transform.
+pub(crate) fn with_current_transform<R>(f: impl FnOnce(&dyn ResponseTransform) -> R) -> R {
+  match scoped::scoped_transform() {
+    Some(transform) => f(transform.as_ref()),
+    None => f((**RESPONSE_TRANSFORM.load()).as_ref()),
+  }
+}
+
 #[doc(hidden)]
 pub fn apply_global_transform(
   name: &str,
@@ -126,39 +776,41 @@ pub fn apply_global_transform(
   _type: Option<String>,
   details: Option<serde_json::Value>,
 ) -> HttpResponse {
-  ResponseTransform::transform(
-    (**RESPONSE_TRANSFORM.load()).as_ref(),
+  apply_global_transform_with_context(&ErrorContext::from_parts(
     name,
     err,
     status_code,
     reason,
     _type,
     details,
-  )
+  ))
 }
 
 #[doc(hidden)]
-pub fn default_global_error_status_code() -> actix_web::http::StatusCode {
-  ResponseTransform::default_error_status_code((**RESPONSE_TRANSFORM.load()).as_ref())
+pub fn apply_global_transform_with_context(context: &ErrorContext) -> HttpResponse {
+  with_current_transform(|transform| transform.transform_with_context(context))
 }
 
-#[doc(hidden)]
-pub trait ThiserrorResponse {
-  fn status_code(&self) -> Option<actix_web::http::StatusCode> {
-    None
-  }
+/// Logs an error, renders it with the current transform and hands it to the
+/// observers, as the derive does.
+pub(crate) fn respond(context: &ErrorContext, debug: &dyn std::fmt::Debug) -> HttpResponse {
+  log::error!(
+    "Response error [{correlation_id}]: {err}\n\t{name}({debug:?})",
+    correlation_id = context.correlation_id,
+    name = context.name,
+    err = context.err,
+  );
 
-  fn reason(&self) -> Option<Option<serde_json::Value>> {
-    None
-  }
+  let response = normalizer::mark_rendered(apply_global_transform_with_context(context));
 
-  fn _type(&self) -> Option<Option<String>> {
-    None
-  }
+  observe_error(context);
 
-  fn details(&self) -> Option<Option<serde_json::Value>> {
-    None
-  }
+  response
+}
+
+#[doc(hidden)]
+pub fn default_global_error_status_code() -> actix_web::http::StatusCode {
+  with_current_transform(|transform| transform.default_error_status_code())
 }
 
 #[allow(unused_imports)]
@@ -169,3 +821,7 @@ extern crate actix_web_thiserror_derive;
 ///
 /// [thiserror]: https://docs.rs/thiserror
 pub use actix_web_thiserror_derive::ResponseError;
+
+/// The derive implementation of [`FromErrorResponse`][trait@FromErrorResponse],
+/// for enums also deriving [`ResponseError`][derive@ResponseError].
+pub use actix_web_thiserror_derive::FromErrorResponse;
@@ -0,0 +1,350 @@
+//! Localized error responses.
+//!
+//! [`LocalizingTransform`] treats the `reason` of an error as a message key,
+//! looks it up in Fluent (`.ftl`) or JSON message catalogs and renders the
+//! result into the response body. The locale is negotiated against the
+//! `Accept-Language` header captured by the [`Localization`] middleware.
+//!
+//! ```rust,no_run
+//! use actix_web::{App, HttpServer};
+//! use actix_web_thiserror::localization::{Localization, LocalizingTransform};
+//!
+//! # async fn run() -> std::io::Result<()> {
+//! actix_web_thiserror::set_global_transform(
+//!   LocalizingTransform::new("en-US")
+//!     .with_dir("locales")?,
+//! );
+//!
+//! HttpServer::new(|| App::new().wrap(Localization))
+//!   .bind(("127.0.0.1", 8080))?
+//!   .run()
+//!   .await
+//! # }
+//! ```
+//!
+//! Variant fields passed through `details` are available to the messages:
+//! every key of an object as its own argument, anything else as `details`.
+
+use std::collections::{BTreeMap, HashMap};
+use std::future::{ready, Future, Ready};
+use std::path::Path;
+use std::pin::Pin;
+
+use actix_web::dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform};
+use actix_web::http::header::{self, AcceptLanguage, Header as _};
+use actix_web::HttpResponse;
+use fluent_bundle::concurrent::FluentBundle;
+use fluent_bundle::{FluentArgs, FluentResource, FluentValue};
+use unic_langid::LanguageIdentifier;
+
+use crate::{ErrorContext, ResponseTransform};
+
+tokio::task_local! {
+  static ACCEPTED_LANGUAGES: Vec<String>;
+}
+
+/// Returns the languages accepted by the client of the current request, most
+/// preferred first.
+///
+/// This is empty outside of the [`Localization`] middleware.
+pub fn accepted_languages() -> Vec<String> {
+  ACCEPTED_LANGUAGES
+    .try_with(|languages| languages.to_owned())
+    .unwrap_or_default()
+}
+
+/// The messages of a single locale.
+enum MessageCatalog {
+  Fluent(Box<FluentBundle<FluentResource>>),
+  Json(HashMap<String, String>),
+}
+
+impl MessageCatalog {
+  fn format(&self, key: &str, details: Option<&serde_json::Value>) -> Option<String> {
+    match self {
+      MessageCatalog::Fluent(bundle) => {
+        let pattern = bundle.get_message(key)?.value()?;
+        let mut args = FluentArgs::new();
+
+        for (name, value) in arguments(details) {
+          args.set(
+            name,
+            match value {
+              serde_json::Value::String(value) => FluentValue::from(value.to_owned()),
+              serde_json::Value::Number(value) => value
+                .as_f64()
+                .map(FluentValue::from)
+                .unwrap_or_else(|| FluentValue::from(value.to_string())),
+              value => FluentValue::from(value.to_string()),
+            },
+          );
+        }
+
+        let mut errors = vec![];
+        let message = bundle.format_pattern(pattern, Some(&args), &mut errors);
+
+        Some(message.into_owned())
+      }
+
+      MessageCatalog::Json(messages) => {
+        let message = messages.get(key)?;
+
+        Some(
+          arguments(details)
+            .into_iter()
+            .fold(message.to_owned(), |message, (name, value)| {
+              let value = match value {
+                serde_json::Value::String(value) => value.to_owned(),
+                value => value.to_string(),
+              };
+
+              message.replace(&format!("{{{}}}", name), &value)
+            }),
+        )
+      }
+    }
+  }
+}
+
+fn arguments(details: Option<&serde_json::Value>) -> Vec<(String, &serde_json::Value)> {
+  match details {
+    Some(serde_json::Value::Object(fields)) => fields
+      .iter()
+      .map(|(name, value)| (name.to_owned(), value))
+      .collect(),
+    Some(details) => vec![("details".to_owned(), details)],
+    None => vec![],
+  }
+}
+
+fn invalid_data(path: &Path, err: impl std::fmt::Display) -> std::io::Error {
+  std::io::Error::new(
+    std::io::ErrorKind::InvalidData,
+    format!("{}: {}", path.display(), err),
+  )
+}
+
+/// A [`ResponseTransform`] that localizes the `reason` of errors.
+///
+/// The body is a JSON object holding the `reason` and the localized `message`,
+/// and the chosen locale is sent back in `Content-Language`. When no catalog has
+/// a message for the reason, the message exposed by the
+/// [`ErrorContext`] is used instead, then the canonical
+/// reason of the status code.
+pub struct LocalizingTransform {
+  default_locale: String,
+  catalogs: BTreeMap<String, MessageCatalog>,
+}
+
+impl LocalizingTransform {
+  /// Creates a transform without any messages, falling back to
+  /// `default_locale` when none of the accepted languages are available.
+  pub fn new(default_locale: impl Into<String>) -> Self {
+    Self {
+      default_locale: default_locale.into(),
+      catalogs: BTreeMap::new(),
+    }
+  }
+
+  /// Loads the messages of `locale` from a Fluent (`.ftl`) file.
+  pub fn with_fluent(
+    mut self,
+    locale: impl Into<String>,
+    path: impl AsRef<Path>,
+  ) -> std::io::Result<Self> {
+    let path = path.as_ref();
+    let locale = locale.into();
+    let language = locale
+      .parse::<LanguageIdentifier>()
+      .map_err(|err| invalid_data(path, err))?;
+    let resource = FluentResource::try_new(std::fs::read_to_string(path)?)
+      .map_err(|(_, errors)| invalid_data(path, format!("{:?}", errors)))?;
+
+    let mut bundle = FluentBundle::new_concurrent(vec![language]);
+    bundle.set_use_isolating(false);
+    bundle
+      .add_resource(resource)
+      .map_err(|errors| invalid_data(path, format!("{:?}", errors)))?;
+
+    self
+      .catalogs
+      .insert(locale, MessageCatalog::Fluent(Box::new(bundle)));
+
+    Ok(self)
+  }
+
+  /// Loads the messages of `locale` from a JSON file mapping keys to messages.
+  ///
+  /// Arguments are interpolated with `{name}` placeholders.
+  pub fn with_json(
+    mut self,
+    locale: impl Into<String>,
+    path: impl AsRef<Path>,
+  ) -> std::io::Result<Self> {
+    let path = path.as_ref();
+    let messages = serde_json::from_str(&std::fs::read_to_string(path)?)
+      .map_err(|err| invalid_data(path, err))?;
+
+    self
+      .catalogs
+      .insert(locale.into(), MessageCatalog::Json(messages));
+
+    Ok(self)
+  }
+
+  /// Loads every `<locale>.ftl` and `<locale>.json` file of a directory.
+  pub fn with_dir(self, path: impl AsRef<Path>) -> std::io::Result<Self> {
+    std::fs::read_dir(path)?.try_fold(self, |transform, entry| {
+      let path = entry?.path();
+      let Some(locale) = path.file_stem().and_then(|stem| stem.to_str()) else {
+        return Ok(transform);
+      };
+      let locale = locale.to_owned();
+
+      match path.extension().and_then(|extension| extension.to_str()) {
+        Some("ftl") => transform.with_fluent(locale, &path),
+        Some("json") => transform.with_json(locale, &path),
+        _ => Ok(transform),
+      }
+    })
+  }
+
+  /// Picks the best available locale for the accepted languages.
+  ///
+  /// A language without an exact match falls back to the first locale, in
+  /// alphabetical order, sharing its primary language, so `en` picks `en-GB`
+  /// over `en-US`.
+  pub fn negotiate(&self, accepted: &[String]) -> &str {
+    let primary = |locale: &str| {
+      locale
+        .split(['-', '_'])
+        .next()
+        .unwrap_or_default()
+        .to_lowercase()
+    };
+
+    accepted
+      .iter()
+      .find_map(|language| {
+        self
+          .catalogs
+          .keys()
+          .find(|locale| locale.eq_ignore_ascii_case(language))
+          .or_else(|| {
+            self
+              .catalogs
+              .keys()
+              .find(|locale| primary(locale) == primary(language))
+          })
+      })
+      .map(|locale| locale as &str)
+      .unwrap_or(&self.default_locale)
+  }
+
+  /// Formats the message for `key` in `locale`, if there is one.
+  pub fn localize(
+    &self,
+    locale: &str,
+    key: &str,
+    details: Option<&serde_json::Value>,
+  ) -> Option<String> {
+    self.catalogs.get(locale)?.format(key, details)
+  }
+}
+
+impl ResponseTransform for LocalizingTransform {
+  fn transform(
+    &self,
+    name: &str,
+    err: &dyn std::error::Error,
+    status_code: actix_web::http::StatusCode,
+    reason: Option<serde_json::Value>,
+    _type: Option<String>,
+    details: Option<serde_json::Value>,
+  ) -> HttpResponse {
+    self.transform_with_context(&ErrorContext::from_parts(
+      name,
+      err,
+      status_code,
+      reason,
+      _type,
+      details,
+    ))
+  }
+
+  fn transform_with_context(&self, context: &ErrorContext) -> HttpResponse {
+    let locale = self.negotiate(&accepted_languages()).to_owned();
+    let key = match &context.reason {
+      Some(serde_json::Value::String(reason)) => Some(reason.to_owned()),
+      Some(reason) => Some(reason.to_string()),
+      None => None,
+    };
+    let message = key
+      .and_then(|key| self.localize(&locale, &key, context.details.as_ref()))
+      .or_else(|| context.message())
+      .or_else(|| {
+        context
+          .status_code
+          .canonical_reason()
+          .map(|reason| reason.to_owned())
+      });
+
+    HttpResponse::build(context.status_code)
+      .insert_header((header::CONTENT_LANGUAGE, locale))
+      .json(serde_json::json!({
+        "reason": context.reason,
+        "message": message,
+      }))
+  }
+}
+
+/// Middleware capturing the `Accept-Language` header of each request for
+/// [`LocalizingTransform`].
+pub struct Localization;
+
+impl<S, B> Transform<S, ServiceRequest> for Localization
+where
+  S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = actix_web::Error>,
+  S::Future: 'static,
+{
+  type Response = ServiceResponse<B>;
+  type Error = actix_web::Error;
+  type Transform = LocalizationMiddleware<S>;
+  type InitError = ();
+  type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+  fn new_transform(&self, service: S) -> Self::Future {
+    ready(Ok(LocalizationMiddleware { service }))
+  }
+}
+
+#[doc(hidden)]
+pub struct LocalizationMiddleware<S> {
+  service: S,
+}
+
+impl<S, B> Service<ServiceRequest> for LocalizationMiddleware<S>
+where
+  S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = actix_web::Error>,
+  S::Future: 'static,
+{
+  type Response = ServiceResponse<B>;
+  type Error = actix_web::Error;
+  type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>>>>;
+
+  forward_ready!(service);
+
+  fn call(&self, req: ServiceRequest) -> Self::Future {
+    let languages = AcceptLanguage::parse(&req)
+      .map(|accept_language| {
+        accept_language
+          .ranked()
+          .into_iter()
+          .filter_map(|language| language.item().map(|language| language.to_string()))
+          .collect()
+      })
+      .unwrap_or_default();
+
+    Box::pin(ACCEPTED_LANGUAGES.scope(languages, self.service.call(req)))
+  }
+}
@@ -0,0 +1,175 @@
+use actix_web::http::StatusCode;
+use serde::Serialize;
+
+/// A piece of [`ResponseMeta`] that an error may leave to others.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum MetaValue<T> {
+  /// The error has no say: the value comes from the error it forwards to, if
+  /// any, or is left out of the response.
+  #[default]
+  Unset,
+  /// The value is explicitly left out of the response, even when the error
+  /// forwards to one that has it.
+  Omitted,
+  /// The value of the error.
+  Set(T),
+}
+
+impl<T> MetaValue<T> {
+  /// Whether the error has no say on the value.
+  pub fn is_unset(&self) -> bool {
+    matches!(self, Self::Unset)
+  }
+
+  /// Returns `self`, or `other` when it is unset.
+  pub fn or(self, other: Self) -> Self {
+    match self {
+      Self::Unset => other,
+      value => value,
+    }
+  }
+
+  /// The value that ends up in the response.
+  pub fn into_option(self) -> Option<T> {
+    match self {
+      Self::Set(value) => Some(value),
+      Self::Unset | Self::Omitted => None,
+    }
+  }
+}
+
+impl<T> From<Option<T>> for MetaValue<T> {
+  /// Sets the value, or omits it when there is none.
+  fn from(value: Option<T>) -> Self {
+    match value {
+      Some(value) => Self::Set(value),
+      None => Self::Omitted,
+    }
+  }
+}
+
+/// What an error contributes to its response, on top of what the transform
+/// decides.
+///
+/// Everything is unset by default. Unset values are taken from the error being
+/// forwarded to, if any, and otherwise fall back to the defaults: the default
+/// status code of the transform, and no reason, type, details or public
+/// message.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[non_exhaustive]
+pub struct ResponseMeta {
+  /// The status code of the response, `None` when unset.
+  pub status_code: Option<StatusCode>,
+  /// The `reason` of the error.
+  pub reason: MetaValue<serde_json::Value>,
+  /// The `type` of the error.
+  pub _type: MetaValue<String>,
+  /// The `details` of the error.
+  pub details: MetaValue<serde_json::Value>,
+  /// The message that is safe to show to clients.
+  pub public_message: MetaValue<String>,
+}
+
+impl ResponseMeta {
+  /// Creates metadata with everything unset.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Sets the status code.
+  pub fn status_code(mut self, status_code: StatusCode) -> Self {
+    self.status_code = Some(status_code);
+    self
+  }
+
+  /// Sets the `reason`, omitting it when it fails to serialize.
+  pub fn reason(mut self, reason: impl Serialize) -> Self {
+    self.reason = serde_json::to_value(reason).ok().into();
+    self
+  }
+
+  /// Sets the `type`.
+  pub fn _type(mut self, _type: impl Into<String>) -> Self {
+    self._type = MetaValue::Set(_type.into());
+    self
+  }
+
+  /// Sets the `details`, omitting them when they fail to serialize.
+  pub fn details(mut self, details: impl Serialize) -> Self {
+    self.details = serde_json::to_value(details).ok().into();
+    self
+  }
+
+  /// Sets the message that is safe to show to clients.
+  pub fn public_message(mut self, public_message: impl Into<String>) -> Self {
+    self.public_message = MetaValue::Set(public_message.into());
+    self
+  }
+
+  /// Fills everything unset in `self` from `other`, as when forwarding to the
+  /// error `other` describes.
+  pub fn or(self, other: Self) -> Self {
+    Self {
+      status_code: self.status_code.or(other.status_code),
+      reason: self.reason.or(other.reason),
+      _type: self._type.or(other._type),
+      details: self.details.or(other.details),
+      public_message: self.public_message.or(other.public_message),
+    }
+  }
+}
+
+/// The response metadata of an error, which `#[response(forward)]` reads
+/// through.
+///
+/// The derive implements this for every `ResponseError` enum, returning the
+/// options of the variant merged with those of the error it forwards to. It
+/// can be implemented by hand for errors the derive does not cover, so that
+/// derived enums can forward to them:
+///
+/// ```rust
+/// use actix_web::http::StatusCode;
+/// use actix_web_thiserror::{ResponseError, ResponseMeta, ThiserrorResponse};
+/// use thiserror::Error;
+///
+/// #[derive(Debug, Error)]
+/// #[error(transparent)]
+/// pub struct DatabaseError(std::io::Error);
+///
+/// impl ThiserrorResponse for DatabaseError {
+///   fn response_meta(&self) -> ResponseMeta {
+///     match self.0.kind() {
+///       std::io::ErrorKind::NotFound => ResponseMeta::new()
+///         .status_code(StatusCode::NOT_FOUND)
+///         .reason("NOT_FOUND"),
+///       _ => ResponseMeta::new().reason("DATABASE_ERROR"),
+///     }
+///   }
+/// }
+///
+/// #[derive(Debug, Error, ResponseError)]
+/// pub enum UserError {
+///   #[response(forward)]
+///   #[error(transparent)]
+///   Database(DatabaseError),
+/// }
+/// ```
+pub trait ThiserrorResponse {
+  /// The response metadata of the error.
+  fn response_meta(&self) -> ResponseMeta;
+
+  /// The `Display` text of the error with its `#[response(sensitive)]` fields
+  /// masked, when it differs from the original. Implemented by the derive, and
+  /// read through forwarded variants.
+  #[doc(hidden)]
+  fn redacted_display(&self) -> Option<String> {
+    None
+  }
+
+  /// The `Debug` text of the error with its `#[response(sensitive)]` fields
+  /// masked, when it differs from the original.
+  #[doc(hidden)]
+  fn redacted_debug(&self) -> Option<String> {
+    None
+  }
+}
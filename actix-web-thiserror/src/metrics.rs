@@ -0,0 +1,38 @@
+//! Counters of emitted errors.
+//!
+//! Every error turned into a response increments
+//! [`ERRORS_TOTAL`], labeled by `error` (the enum name), `variant`, `status`
+//! and `reason`, through the [`metrics`](https://docs.rs/metrics) facade.
+//! Install any `metrics` recorder, such as a Prometheus exporter, to collect
+//! them.
+
+use crate::{ErrorContext, ErrorObserver};
+
+/// The name of the counter of emitted errors.
+pub const ERRORS_TOTAL: &str = "actix_web_thiserror_errors_total";
+
+/// The [`ErrorObserver`] incrementing [`ERRORS_TOTAL`].
+///
+/// It observes every error while the `metrics` feature is enabled, before the
+/// observers registered with
+/// [`register_error_observer`][crate::register_error_observer].
+pub struct MetricsObserver;
+
+impl ErrorObserver for MetricsObserver {
+  fn observe(&self, context: &ErrorContext) {
+    let reason = match &context.reason {
+      Some(serde_json::Value::String(reason)) => reason.to_owned(),
+      Some(reason) => reason.to_string(),
+      None => String::new(),
+    };
+
+    metrics::counter!(
+      ERRORS_TOTAL,
+      "error" => context.name.to_owned(),
+      "variant" => context.variant.to_owned(),
+      "status" => context.status_code.as_u16().to_string(),
+      "reason" => reason,
+    )
+    .increment(1);
+  }
+}
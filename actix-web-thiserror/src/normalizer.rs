@@ -0,0 +1,240 @@
+use std::future::{ready, Future, Ready};
+use std::pin::Pin;
+
+use actix_web::body::{BodySize, EitherBody, MessageBody};
+use actix_web::dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform};
+use actix_web::error::InternalError;
+use actix_web::http::header::{HeaderMap, CONTENT_LENGTH, CONTENT_TYPE};
+use actix_web::http::StatusCode;
+use actix_web::{HttpResponse, ResponseError as _};
+
+use crate::{ErrorContext, ResponseMeta, ThiserrorResponse};
+
+/// Marks the responses rendered by this crate, which [`ErrorNormalizer`] lets
+/// through.
+struct Rendered;
+
+/// Marks a response as rendered by this crate.
+#[doc(hidden)]
+pub fn mark_rendered(mut response: HttpResponse) -> HttpResponse {
+  response.extensions_mut().insert(Rendered);
+  response
+}
+
+fn is_rendered<B>(response: &actix_web::HttpResponse<B>) -> bool {
+  response.extensions().get::<Rendered>().is_some()
+}
+
+/// An error response that was not rendered by this crate, such as the 404 of an
+/// unmatched route or an error of a third-party middleware.
+///
+/// Its reason is derived from its status code, e.g. `NOT_FOUND` or
+/// `METHOD_NOT_ALLOWED`.
+#[derive(Clone, Debug)]
+pub struct NormalizedError {
+  status_code: StatusCode,
+  message: Option<String>,
+}
+
+impl NormalizedError {
+  /// Creates an error with the given status code.
+  pub fn new(status_code: StatusCode) -> Self {
+    Self {
+      status_code,
+      message: None,
+    }
+  }
+
+  /// Sets the `Display` text of the error, which defaults to the canonical
+  /// reason of the status code.
+  pub fn message(mut self, message: impl Into<String>) -> Self {
+    self.message = Some(message.into());
+    self
+  }
+
+  /// The `reason` of the error, from the canonical reason of its status code.
+  pub fn reason(&self) -> String {
+    match self.status_code.canonical_reason() {
+      Some(reason) => reason
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| word.to_ascii_uppercase())
+        .collect::<Vec<_>>()
+        .join("_"),
+      None => format!("HTTP_{}", self.status_code.as_u16()),
+    }
+  }
+}
+
+impl std::fmt::Display for NormalizedError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match &self.message {
+      Some(message) => f.write_str(message),
+      None => f.write_str(
+        self
+          .status_code
+          .canonical_reason()
+          .unwrap_or("Unknown error"),
+      ),
+    }
+  }
+}
+
+impl std::error::Error for NormalizedError {}
+
+impl ThiserrorResponse for NormalizedError {
+  fn response_meta(&self) -> ResponseMeta {
+    ResponseMeta::new()
+      .status_code(self.status_code)
+      .reason(self.reason())
+  }
+}
+
+impl actix_web::ResponseError for NormalizedError {
+  fn status_code(&self) -> StatusCode {
+    self.status_code
+  }
+
+  fn error_response(&self) -> HttpResponse {
+    let mut context = ErrorContext::new("NormalizedError", self, self.status_code);
+    context.set_meta(self.response_meta());
+
+    crate::respond(&context, self)
+  }
+}
+
+/// A default service rendering a 404 through the transform, for routes that do
+/// not match any other.
+///
+/// ```rust
+/// use actix_web::App;
+///
+/// let app = App::new().default_service(actix_web_thiserror::default_service());
+/// ```
+pub fn default_service() -> actix_web::Route {
+  actix_web::web::to(|| async {
+    Err::<HttpResponse, _>(NormalizedError::new(StatusCode::NOT_FOUND))
+  })
+}
+
+/// Middleware rendering the error responses that were not rendered by this
+/// crate through the transform, as a [`NormalizedError`].
+///
+/// This covers responses carrying an `actix_web::Error`, such as those of
+/// third-party middleware, and error responses without a body, such as the 404
+/// of unmatched routes and the 405 of unmatched methods, as well as the errors
+/// that inner middleware return instead of a response. Error responses with a
+/// body of their own are left untouched. The headers of normalized responses
+/// are kept, other than `Content-Type` and `Content-Length`.
+///
+/// ```rust
+/// use actix_web::App;
+/// use actix_web_thiserror::ErrorNormalizer;
+///
+/// let app = App::new().wrap(ErrorNormalizer);
+/// ```
+pub struct ErrorNormalizer;
+
+impl<S, B> Transform<S, ServiceRequest> for ErrorNormalizer
+where
+  S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = actix_web::Error>,
+  S::Future: 'static,
+  B: MessageBody,
+{
+  type Response = ServiceResponse<EitherBody<B>>;
+  type Error = actix_web::Error;
+  type Transform = ErrorNormalizerMiddleware<S>;
+  type InitError = ();
+  type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+  fn new_transform(&self, service: S) -> Self::Future {
+    ready(Ok(ErrorNormalizerMiddleware { service }))
+  }
+}
+
+#[doc(hidden)]
+pub struct ErrorNormalizerMiddleware<S> {
+  service: S,
+}
+
+impl<S, B> Service<ServiceRequest> for ErrorNormalizerMiddleware<S>
+where
+  S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = actix_web::Error>,
+  S::Future: 'static,
+  B: MessageBody,
+{
+  type Response = ServiceResponse<EitherBody<B>>;
+  type Error = actix_web::Error;
+  type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>>>>;
+
+  forward_ready!(service);
+
+  fn call(&self, req: ServiceRequest) -> Self::Future {
+    let fut = self.service.call(req);
+
+    Box::pin(async move {
+      let res = match fut.await {
+        Ok(res) => res,
+        Err(err) => return Err(normalize_error(err)),
+      };
+
+      let response = res.response();
+      let normalize_response = response.status().as_u16() >= 400
+        && !is_rendered(response)
+        && (response.error().is_some()
+          || matches!(response.body().size(), BodySize::None | BodySize::Sized(0)));
+
+      match normalize_response {
+        true => {
+          let mut normalized = normalize(response.status(), response.error());
+          copy_headers(response.headers(), &mut normalized);
+
+          Ok(res.into_response(normalized).map_into_right_body())
+        }
+        false => Ok(res.map_into_left_body()),
+      }
+    })
+  }
+}
+
+fn normalize(status_code: StatusCode, err: Option<&actix_web::Error>) -> HttpResponse {
+  let normalized = NormalizedError::new(status_code);
+  let normalized = match err {
+    Some(err) => normalized.message(err.to_string()),
+    None => normalized,
+  };
+
+  normalized.error_response()
+}
+
+/// Normalizes an error returned instead of a response, unless this crate
+/// rendered it. The response is rendered here, within the outer middleware, as
+/// actix would otherwise render the error after them.
+fn normalize_error(err: actix_web::Error) -> actix_web::Error {
+  let response = err.error_response();
+  let response = match is_rendered(&response) || response.status().as_u16() < 400 {
+    true => response,
+    false => {
+      let mut normalized = normalize(response.status(), Some(&err));
+      copy_headers(response.headers(), &mut normalized);
+      normalized
+    }
+  };
+
+  InternalError::from_response(err, response).into()
+}
+
+/// Copies the headers of the original response, such as `WWW-Authenticate`,
+/// `Allow` or `Retry-After`, onto the normalized one. The headers describing
+/// the body, and those the transform set, are kept from the normalized one.
+fn copy_headers(headers: &HeaderMap, normalized: &mut HttpResponse) {
+  let rendered = normalized.headers().keys().cloned().collect::<Vec<_>>();
+
+  for (name, value) in headers {
+    if name != CONTENT_TYPE && name != CONTENT_LENGTH && !rendered.contains(name) {
+      normalized
+        .headers_mut()
+        .append(name.to_owned(), value.to_owned());
+    }
+  }
+}
@@ -0,0 +1,65 @@
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::Arc;
+
+use arc_swap::ArcSwap;
+use lazy_static::lazy_static;
+
+use crate::ErrorContext;
+
+/// Receives every error turned into a response, to forward it to metrics,
+/// tracing or error tracking.
+///
+/// Observers run after the transform built the response, with the same
+/// [`ErrorContext`]. The source chain of the error is available through
+/// `context.err`.
+pub trait ErrorObserver {
+  fn observe(&self, context: &ErrorContext);
+}
+
+type Observers = Vec<Arc<dyn ErrorObserver + Sync + Send>>;
+
+#[cfg(feature = "metrics")]
+fn builtin_observers() -> Observers {
+  vec![Arc::new(crate::metrics::MetricsObserver)]
+}
+
+#[cfg(not(feature = "metrics"))]
+fn builtin_observers() -> Observers {
+  vec![]
+}
+
+lazy_static! {
+  static ref ERROR_OBSERVERS: ArcSwap<Observers> = ArcSwap::from_pointee(builtin_observers());
+}
+
+/// Registers an observer of errors turned into responses.
+///
+/// Observers are called in the order they were registered. An observer that
+/// panics is skipped, and the remaining observers and the response are left
+/// untouched.
+pub fn register_error_observer(observer: impl ErrorObserver + Sync + Send + 'static) {
+  let observer: Arc<dyn ErrorObserver + Sync + Send> = Arc::new(observer);
+
+  ERROR_OBSERVERS.rcu(|observers| {
+    let mut observers = Observers::clone(observers);
+    observers.push(observer.to_owned());
+
+    observers
+  });
+}
+
+#[doc(hidden)]
+pub fn observe_error(context: &ErrorContext) {
+  #[cfg(feature = "test-util")]
+  crate::test_util::capture(context);
+
+  for observer in ERROR_OBSERVERS.load().iter() {
+    if catch_unwind(AssertUnwindSafe(|| observer.observe(context))).is_err() {
+      log::error!(
+        "Error observer panicked while observing {}::{}",
+        context.name,
+        context.variant
+      );
+    }
+  }
+}
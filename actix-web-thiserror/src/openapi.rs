@@ -0,0 +1,140 @@
+//! Documents error enums as [utoipa] responses.
+//!
+//! With the `openapi` feature enabled, `#[derive(ResponseError)]` also
+//! implements [`utoipa::IntoResponses`], so an error enum can be listed in
+//! `#[utoipa::path(responses(...))]` directly. Variants are grouped by status
+//! code, and the schema and examples of each response come from the
+//! [`ResponseTransform`] in effect when the
+//! document is built, or from the transform an enum or variant sets with
+//! `#[response(transform = ...)]`.
+//!
+//! [utoipa]: https://docs.rs/utoipa
+//! [`ResponseTransform`]: crate::ResponseTransform
+
+use std::collections::BTreeMap;
+
+use utoipa::openapi::example::ExampleBuilder;
+use utoipa::openapi::{ContentBuilder, RefOr, Response, ResponseBuilder};
+
+use crate::{with_current_transform, ErrorCatalog, ErrorCatalogEntry, ResponseTransform};
+
+/// Builds the responses for the variants of a catalog, grouped by status code.
+///
+/// Forwarded variants are skipped, their responses are merged in from the
+/// wrapped error with [`merge_responses`].
+pub fn responses(catalog: &ErrorCatalog) -> BTreeMap<String, RefOr<Response>> {
+  with_current_transform(|transform| responses_with(transform, catalog))
+}
+
+/// Builds the responses for the variants of a catalog like [`responses`], with
+/// `transform` instead of the transform in effect.
+///
+/// Variants without a status code of their own are grouped under the default
+/// status code of `transform`.
+pub fn responses_with(
+  transform: &dyn ResponseTransform,
+  catalog: &ErrorCatalog,
+) -> BTreeMap<String, RefOr<Response>> {
+  let mut grouped: BTreeMap<u16, Vec<&ErrorCatalogEntry>> = BTreeMap::new();
+
+  for entry in catalog.entries.iter().filter(|entry| !entry.forward) {
+    let status_code = entry
+      .status
+      .and_then(|status| actix_web::http::StatusCode::from_u16(status).ok())
+      .unwrap_or_else(|| transform.default_error_status_code());
+
+    grouped.entry(status_code.as_u16()).or_default().push(entry);
+  }
+
+  grouped
+    .into_iter()
+    .map(|(status, entries)| {
+      let description = entries
+        .iter()
+        .map(|entry| describe(entry))
+        .collect::<Vec<_>>()
+        .join("\n");
+      let mut response = ResponseBuilder::new().description(description);
+
+      if let Some(schema) = transform.openapi_schema() {
+        let examples = entries.iter().filter_map(|entry| {
+          transform.openapi_example(entry).map(|example| {
+            (
+              format!("{}::{}", catalog.name, entry.variant),
+              ExampleBuilder::new()
+                .summary(entry.display.unwrap_or(entry.variant))
+                .value(Some(example))
+                .build(),
+            )
+          })
+        });
+
+        response = response.content(
+          transform.openapi_content_type(),
+          ContentBuilder::new()
+            .schema(Some(schema))
+            .examples_from_iter(examples)
+            .build(),
+        );
+      }
+
+      (status.to_string(), RefOr::T(response.build()))
+    })
+    .collect()
+}
+
+/// A copy of `catalog` with only the given variants, for documenting them with
+/// a transform of their own.
+#[doc(hidden)]
+pub fn select(catalog: &ErrorCatalog, variants: &[&str]) -> ErrorCatalog {
+  ErrorCatalog {
+    name: catalog.name,
+    entries: catalog
+      .entries
+      .iter()
+      .filter(|entry| variants.contains(&entry.variant))
+      .cloned()
+      .collect(),
+  }
+}
+
+/// Merges the responses of a forwarded error into `responses`, joining the
+/// descriptions and examples of responses sharing a status code.
+pub fn merge_responses(
+  responses: &mut BTreeMap<String, RefOr<Response>>,
+  other: BTreeMap<String, RefOr<Response>>,
+) {
+  for (status, other) in other {
+    match (responses.get_mut(&status), other) {
+      (Some(RefOr::T(response)), RefOr::T(other)) => {
+        response.description = format!("{}\n{}", response.description, other.description);
+
+        for (content_type, other) in other.content {
+          match response.content.get_mut(&content_type) {
+            Some(content) => content.examples.extend(other.examples),
+            None => {
+              response.content.insert(content_type, other);
+            }
+          }
+        }
+      }
+      (Some(_), _) => {}
+      (None, other) => {
+        responses.insert(status, other);
+      }
+    }
+  }
+}
+
+fn describe(entry: &ErrorCatalogEntry) -> String {
+  let name = match &entry.reason {
+    Some(serde_json::Value::String(reason)) => reason.to_owned(),
+    Some(reason) => reason.to_string(),
+    None => entry.variant.to_owned(),
+  };
+
+  match entry.display {
+    Some(display) => format!("- `{}`: {}", name, display),
+    None => format!("- `{}`", name),
+  }
+}
@@ -0,0 +1,205 @@
+use std::any::Any;
+use std::future::{ready, Future, Ready};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use actix_web::dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform};
+use actix_web::error::InternalError;
+use actix_web::http::StatusCode;
+use actix_web::HttpResponse;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{ErrorContext, ResponseMeta, ThiserrorResponse};
+
+/// A panic caught by [`CatchPanic`], responding with a 500.
+#[derive(Clone, Debug)]
+pub struct PanicError {
+  message: String,
+  reason: Option<Value>,
+}
+
+impl PanicError {
+  fn new(payload: Box<dyn Any + Send>, reason: Option<Value>) -> Self {
+    let message = match payload.downcast::<String>() {
+      Ok(message) => *message,
+      Err(payload) => match payload.downcast::<&'static str>() {
+        Ok(message) => (*message).to_owned(),
+        Err(_) => "Box<dyn Any>".to_owned(),
+      },
+    };
+
+    Self { message, reason }
+  }
+
+  /// The message the handler panicked with.
+  pub fn message(&self) -> &str {
+    &self.message
+  }
+}
+
+impl std::fmt::Display for PanicError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "handler panicked: {}", self.message)
+  }
+}
+
+impl std::error::Error for PanicError {}
+
+impl ThiserrorResponse for PanicError {
+  fn response_meta(&self) -> ResponseMeta {
+    let meta = ResponseMeta::new().status_code(StatusCode::INTERNAL_SERVER_ERROR);
+
+    match &self.reason {
+      Some(reason) => meta.reason(reason),
+      None => meta,
+    }
+  }
+}
+
+impl actix_web::ResponseError for PanicError {
+  fn status_code(&self) -> StatusCode {
+    StatusCode::INTERNAL_SERVER_ERROR
+  }
+
+  fn error_response(&self) -> HttpResponse {
+    let mut context = ErrorContext::new("PanicError", self, StatusCode::INTERNAL_SERVER_ERROR);
+    context.set_meta(self.response_meta());
+
+    crate::respond(&context, self)
+  }
+}
+
+/// Middleware catching the panics of handlers and inner middleware, which are
+/// logged, rendered by the transform and observed as a [`PanicError`] with a
+/// 500 and the `PANIC` reason.
+///
+/// Panics are rendered within the middleware wrapping this one, so responses
+/// get the correlation id of [`RequestId`](crate::RequestId) when it wraps
+/// `CatchPanic`.
+///
+/// Only unwinding panics are caught, so this does nothing with
+/// `panic = "abort"`.
+///
+/// ```rust
+/// use actix_web::App;
+/// use actix_web_thiserror::CatchPanic;
+///
+/// let app = App::new().wrap(CatchPanic::new().reason("INTERNAL_PANIC"));
+/// ```
+#[derive(Clone, Debug)]
+pub struct CatchPanic {
+  reason: Option<Value>,
+}
+
+impl CatchPanic {
+  /// Creates the middleware with the `PANIC` reason.
+  pub fn new() -> Self {
+    Self {
+      reason: Some(Value::from("PANIC")),
+    }
+  }
+
+  /// Sets the `reason` of the responses, omitting it when it fails to
+  /// serialize.
+  pub fn reason(mut self, reason: impl Serialize) -> Self {
+    self.reason = serde_json::to_value(reason).ok();
+    self
+  }
+}
+
+impl Default for CatchPanic {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for CatchPanic
+where
+  S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = actix_web::Error>,
+  S::Future: 'static,
+  B: 'static,
+{
+  type Response = ServiceResponse<B>;
+  type Error = actix_web::Error;
+  type Transform = CatchPanicMiddleware<S>;
+  type InitError = ();
+  type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+  fn new_transform(&self, service: S) -> Self::Future {
+    ready(Ok(CatchPanicMiddleware {
+      service,
+      reason: self.reason.to_owned(),
+    }))
+  }
+}
+
+#[doc(hidden)]
+pub struct CatchPanicMiddleware<S> {
+  service: S,
+  reason: Option<Value>,
+}
+
+impl<S, B> Service<ServiceRequest> for CatchPanicMiddleware<S>
+where
+  S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = actix_web::Error>,
+  S::Future: 'static,
+  B: 'static,
+{
+  type Response = ServiceResponse<B>;
+  type Error = actix_web::Error;
+  type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>>>>;
+
+  forward_ready!(service);
+
+  fn call(&self, req: ServiceRequest) -> Self::Future {
+    match catch_unwind(AssertUnwindSafe(|| self.service.call(req))) {
+      Ok(fut) => Box::pin(CatchUnwind {
+        fut: Box::pin(fut),
+        reason: self.reason.to_owned(),
+      }),
+      Err(payload) => {
+        let err = PanicError::new(payload, self.reason.to_owned());
+
+        // Rendered when polled, within the scope of the outer middleware.
+        Box::pin(async move { Err(rendered(err)) })
+      }
+    }
+  }
+}
+
+/// Renders the response of a panic while handling the request, so that it
+/// gets the correlation id of the request.
+///
+/// The request is not kept around to respond with, as routing needs to be the
+/// only owner of it.
+fn rendered(err: PanicError) -> actix_web::Error {
+  let response = actix_web::ResponseError::error_response(&err);
+
+  InternalError::from_response(err, response).into()
+}
+
+struct CatchUnwind<F> {
+  fut: Pin<Box<F>>,
+  reason: Option<Value>,
+}
+
+impl<F, T> Future for CatchUnwind<F>
+where
+  F: Future<Output = Result<T, actix_web::Error>>,
+{
+  type Output = F::Output;
+
+  fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+    let fut = self.fut.as_mut();
+
+    match catch_unwind(AssertUnwindSafe(|| fut.poll(cx))) {
+      Ok(poll) => poll,
+      Err(payload) => Poll::Ready(Err(rendered(PanicError::new(
+        payload,
+        self.reason.to_owned(),
+      )))),
+    }
+  }
+}
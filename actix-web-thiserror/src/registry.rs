@@ -0,0 +1,289 @@
+//! Finding typed errors behind type-erased ones.
+//!
+//! Every non-generic enum deriving [`ResponseError`] is registered while the
+//! `registry` feature is enabled, as are the types passed to
+//! [`register_response!`][crate::register_response]. The registry finds them in
+//! the source chain of an error whose type was erased, such as an
+//! [`anyhow::Error`](https://docs.rs/anyhow) or a `Box<dyn Error>`.
+
+use std::error::Error;
+
+use actix_web::http::StatusCode;
+
+use crate::{ResponseMeta, ThiserrorResponse};
+
+/// A registered error found in a source chain.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct RegisteredError<'a> {
+  /// The name of the error type.
+  pub name: &'static str,
+  /// The name of the variant, empty when the error is not an enum.
+  pub variant: &'static str,
+  /// The error itself.
+  pub err: &'a (dyn Error + 'static),
+  /// The status code the error responds with on its own.
+  pub status_code: StatusCode,
+  /// The response metadata of the error.
+  pub meta: ResponseMeta,
+}
+
+impl<'a> RegisteredError<'a> {
+  /// Finds the registered error an [`actix_web::Error`] was created from,
+  /// including through an `AnyResponse` when the `anyhow` feature is enabled.
+  ///
+  /// This lets middleware work on the structured error rather than on the
+  /// response it was rendered into.
+  pub fn from_actix_error(err: &'a actix_web::Error) -> Option<Self> {
+    let registered = inventory::iter::<ResponseRegistration>
+      .into_iter()
+      .find_map(|registration| (registration.downcast_actix)(err));
+
+    #[cfg(feature = "anyhow")]
+    let registered = registered.or_else(|| {
+      err
+        .as_error::<crate::AnyResponse>()
+        .and_then(|any| find_registered(any.0.as_ref()))
+    });
+
+    registered
+  }
+}
+
+#[doc(hidden)]
+pub type ActixError = actix_web::Error;
+
+#[doc(hidden)]
+pub use actix_web::HttpResponse;
+#[doc(hidden)]
+pub use actix_web::ResponseError as ActixResponseError;
+
+#[doc(hidden)]
+pub struct ResponseRegistration {
+  pub downcast: for<'a> fn(&'a (dyn Error + 'static)) -> Option<RegisteredError<'a>>,
+  pub downcast_actix: for<'a> fn(&'a actix_web::Error) -> Option<RegisteredError<'a>>,
+  pub error_response: fn(&(dyn Error + 'static)) -> Option<HttpResponse>,
+}
+
+inventory::collect!(ResponseRegistration);
+
+/// Finds the first error of the source chain of `err`, starting with `err`
+/// itself, that is registered.
+pub fn find_registered<'a>(err: &'a (dyn Error + 'static)) -> Option<RegisteredError<'a>> {
+  let mut source = Some(err);
+
+  while let Some(err) = source {
+    let registered = inventory::iter::<ResponseRegistration>
+      .into_iter()
+      .find_map(|registration| (registration.downcast)(err));
+
+    if registered.is_some() {
+      return registered;
+    }
+
+    source = err.source();
+  }
+
+  None
+}
+
+/// Renders a registered error found by [`find_registered`] with its own
+/// `actix_web::ResponseError` implementation, and so with its own transform.
+///
+/// Types registered by hand that do not implement it are not rendered.
+pub(crate) fn error_response(err: &(dyn Error + 'static)) -> Option<HttpResponse> {
+  inventory::iter::<ResponseRegistration>
+    .into_iter()
+    .find_map(|registration| (registration.error_response)(err))
+}
+
+/// Describes a type deriving `ResponseError`.
+#[doc(hidden)]
+pub fn registered_derived<'a, T>(
+  typed: &'a T,
+  name: &'static str,
+  variant: &'static str,
+) -> RegisteredError<'a>
+where
+  T: actix_web::ResponseError + ThiserrorResponse + Error + 'static,
+{
+  RegisteredError {
+    name,
+    variant,
+    err: typed,
+    status_code: actix_web::ResponseError::status_code(typed),
+    meta: typed.response_meta(),
+  }
+}
+
+/// Describes a type implementing [`ThiserrorResponse`] by hand.
+#[doc(hidden)]
+pub fn registered<'a, T>(typed: &'a T, name: &'static str) -> RegisteredError<'a>
+where
+  T: ThiserrorResponse + Error + 'static,
+{
+  let meta = typed.response_meta();
+
+  RegisteredError {
+    name,
+    variant: "",
+    err: typed,
+    status_code: meta
+      .status_code
+      .unwrap_or_else(crate::default_global_error_status_code),
+    meta,
+  }
+}
+
+/// Downcasts an [`actix_web::Error`] to a type registered by hand, which may
+/// not implement `actix_web::ResponseError`.
+///
+/// `(&Downcast::<T>(PhantomData)).downcast_actix(..)` resolves to
+/// [`DowncastActix`] when it does, and to [`DowncastActixFallback`], which
+/// finds nothing, otherwise.
+#[doc(hidden)]
+pub struct Downcast<T>(pub std::marker::PhantomData<T>);
+
+#[doc(hidden)]
+pub trait DowncastActix {
+  fn downcast_actix<'a>(
+    &self,
+    err: &'a actix_web::Error,
+    name: &'static str,
+  ) -> Option<RegisteredError<'a>>;
+
+  fn error_response(&self, err: &(dyn Error + 'static)) -> Option<HttpResponse>;
+}
+
+impl<T> DowncastActix for Downcast<T>
+where
+  T: actix_web::ResponseError + ThiserrorResponse + Error + 'static,
+{
+  fn downcast_actix<'a>(
+    &self,
+    err: &'a actix_web::Error,
+    name: &'static str,
+  ) -> Option<RegisteredError<'a>> {
+    err.as_error::<T>().map(|typed| registered(typed, name))
+  }
+
+  fn error_response(&self, err: &(dyn Error + 'static)) -> Option<HttpResponse> {
+    err
+      .downcast_ref::<T>()
+      .map(actix_web::ResponseError::error_response)
+  }
+}
+
+#[doc(hidden)]
+pub trait DowncastActixFallback {
+  fn downcast_actix<'a>(
+    &self,
+    _err: &'a actix_web::Error,
+    _name: &'static str,
+  ) -> Option<RegisteredError<'a>> {
+    None
+  }
+
+  fn error_response(&self, _err: &(dyn Error + 'static)) -> Option<HttpResponse> {
+    None
+  }
+}
+
+impl<T> DowncastActixFallback for &Downcast<T> {}
+
+/// Registers a type implementing [`ThiserrorResponse`] by hand, so that it is
+/// found behind type-erased errors like the derived ones.
+///
+/// ```rust
+/// use actix_web_thiserror::{ResponseMeta, ThiserrorResponse};
+/// use thiserror::Error;
+///
+/// #[derive(Debug, Error)]
+/// #[error("rate limited")]
+/// pub struct RateLimited;
+///
+/// impl ThiserrorResponse for RateLimited {
+///   fn response_meta(&self) -> ResponseMeta {
+///     ResponseMeta::new().reason("RATE_LIMITED")
+///   }
+/// }
+///
+/// actix_web_thiserror::register_response!(RateLimited);
+/// ```
+#[macro_export]
+macro_rules! register_response {
+  ($ty:ty) => {
+    const _: () = {
+      fn downcast<'a>(
+        err: &'a (dyn ::std::error::Error + 'static),
+      ) -> Option<$crate::registry::RegisteredError<'a>> {
+        err
+          .downcast_ref::<$ty>()
+          .map(|typed| $crate::registry::registered(typed, stringify!($ty)))
+      }
+
+      fn downcast_actix<'a>(
+        err: &'a $crate::registry::ActixError,
+      ) -> Option<$crate::registry::RegisteredError<'a>> {
+        #[allow(unused_imports)]
+        use $crate::registry::{DowncastActix as _, DowncastActixFallback as _};
+
+        (&$crate::registry::Downcast::<$ty>(::std::marker::PhantomData))
+          .downcast_actix(err, stringify!($ty))
+      }
+
+      fn error_response(
+        err: &(dyn ::std::error::Error + 'static),
+      ) -> Option<$crate::registry::HttpResponse> {
+        #[allow(unused_imports)]
+        use $crate::registry::{DowncastActix as _, DowncastActixFallback as _};
+
+        (&$crate::registry::Downcast::<$ty>(::std::marker::PhantomData)).error_response(err)
+      }
+
+      $crate::__private::inventory::submit! {
+        $crate::registry::ResponseRegistration { downcast, downcast_actix, error_response }
+      }
+    };
+  };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __submit_registered_error {
+  ($ty:ty, $name:expr, $variant:expr) => {
+    const _: () = {
+      fn variant(err: &$ty) -> &'static str {
+        ($variant)(err)
+      }
+
+      fn downcast<'a>(
+        err: &'a (dyn ::std::error::Error + 'static),
+      ) -> Option<$crate::registry::RegisteredError<'a>> {
+        err
+          .downcast_ref::<$ty>()
+          .map(|typed| $crate::registry::registered_derived(typed, $name, variant(typed)))
+      }
+
+      fn downcast_actix<'a>(
+        err: &'a $crate::registry::ActixError,
+      ) -> Option<$crate::registry::RegisteredError<'a>> {
+        err
+          .as_error::<$ty>()
+          .map(|typed| $crate::registry::registered_derived(typed, $name, variant(typed)))
+      }
+
+      fn error_response(
+        err: &(dyn ::std::error::Error + 'static),
+      ) -> Option<$crate::registry::HttpResponse> {
+        err
+          .downcast_ref::<$ty>()
+          .map($crate::registry::ActixResponseError::error_response)
+      }
+
+      $crate::__private::inventory::submit! {
+        $crate::registry::ResponseRegistration { downcast, downcast_actix, error_response }
+      }
+    };
+  };
+}
@@ -0,0 +1,64 @@
+use std::cell::RefCell;
+use std::marker::PhantomData;
+use std::rc::Rc;
+
+use crate::ResponseTransform;
+
+thread_local! {
+  static TRANSFORM_OVERRIDES: RefCell<Vec<Rc<dyn ResponseTransform>>> = const { RefCell::new(vec![]) };
+}
+
+/// Overrides the global transform on the current thread until it is dropped.
+///
+/// Guards nest: dropping one restores the transform that was in effect when it
+/// was created. As the override is bound to the thread, it is meant for tests
+/// and synchronous code, and must not be held across an `.await` in a server.
+///
+/// ```rust
+/// # use actix_web_thiserror::{ResponseTransform, TransformGuard};
+/// # struct ProblemTransform;
+/// # impl ResponseTransform for ProblemTransform {}
+/// let _guard = TransformGuard::new(ProblemTransform);
+///
+/// // Errors turned into responses on this thread use `ProblemTransform`.
+/// ```
+#[must_use = "the override ends when the guard is dropped"]
+pub struct TransformGuard {
+  depth: usize,
+  _thread_bound: PhantomData<*const ()>,
+}
+
+impl TransformGuard {
+  pub fn new(transform: impl ResponseTransform + 'static) -> Self {
+    let depth = TRANSFORM_OVERRIDES.with(|overrides| {
+      let mut overrides = overrides.borrow_mut();
+      overrides.push(Rc::new(transform));
+
+      overrides.len()
+    });
+
+    Self {
+      depth,
+      _thread_bound: PhantomData,
+    }
+  }
+}
+
+impl Drop for TransformGuard {
+  fn drop(&mut self) {
+    TRANSFORM_OVERRIDES.with(|overrides| overrides.borrow_mut().truncate(self.depth - 1));
+  }
+}
+
+/// Runs `f` with `transform` overriding the global transform on the current
+/// thread, see [`TransformGuard`].
+pub fn with_transform<R>(transform: impl ResponseTransform + 'static, f: impl FnOnce() -> R) -> R {
+  let _guard = TransformGuard::new(transform);
+
+  f()
+}
+
+/// The innermost transform override of the current thread, if any.
+pub(crate) fn scoped_transform() -> Option<Rc<dyn ResponseTransform>> {
+  TRANSFORM_OVERRIDES.with(|overrides| overrides.borrow().last().cloned())
+}
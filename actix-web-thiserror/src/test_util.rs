@@ -0,0 +1,272 @@
+//! Helpers for asserting the responses of errors in tests.
+//!
+//! [`render`] turns an error into its status code and JSON body with the
+//! transform in effect, and [`assert_response!`][crate::assert_response]
+//! checks them along with the reason, type, details and public message the
+//! error was rendered with. [`RecordingTransform`] records every call it receives.
+//!
+//! ```rust
+//! use actix_web_thiserror::{assert_response, ResponseError};
+//! use thiserror::Error;
+//!
+//! #[derive(Debug, Error, ResponseError)]
+//! pub enum UserError {
+//!   #[response(status = 404, reason = "USER_NOT_FOUND")]
+//!   #[error("user not found")]
+//!   NotFound,
+//! }
+//!
+//! assert_response!(UserError::NotFound, status = 404, reason = "USER_NOT_FOUND");
+//! ```
+
+use std::cell::RefCell;
+use std::sync::{Arc, Mutex};
+
+use actix_web::body::MessageBody as _;
+use actix_web::http::StatusCode;
+use actix_web::{HttpResponse, ResponseError};
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{ErrorContext, ResponseTransform};
+
+thread_local! {
+  static CAPTURED: RefCell<Option<Vec<RecordedCall>>> = const { RefCell::new(None) };
+}
+
+/// A call to a transform, as recorded by [`RecordingTransform`].
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct RecordedCall {
+  /// The name of the error type.
+  pub name: String,
+  /// The name of the variant, empty when the error is not an enum.
+  pub variant: String,
+  /// The `Display` text of the error.
+  pub message: String,
+  /// The status code of the response.
+  pub status_code: StatusCode,
+  /// The correlation id of the response.
+  pub correlation_id: String,
+  /// The `reason` of the error, if any.
+  pub reason: Option<Value>,
+  /// The `type` of the error, if any.
+  pub _type: Option<String>,
+  /// The `details` of the error, if any.
+  pub details: Option<Value>,
+  /// The message that is safe to show to clients, if any.
+  pub public_message: Option<String>,
+}
+
+impl RecordedCall {
+  fn new(context: &ErrorContext) -> Self {
+    Self {
+      name: context.name.to_owned(),
+      variant: context.variant.to_owned(),
+      message: context.err.to_string(),
+      status_code: context.status_code,
+      correlation_id: context.correlation_id.to_owned(),
+      reason: context.reason.to_owned(),
+      _type: context._type.to_owned(),
+      details: context.details.to_owned(),
+      public_message: context.public_message.to_owned(),
+    }
+  }
+}
+
+/// A transform recording every call it receives.
+///
+/// Clones share their records, so one can be installed while another is kept
+/// for assertions. It responds with an empty body of the status code, or like
+/// the transform it wraps.
+///
+/// ```rust
+/// use actix_web::ResponseError as _;
+/// use actix_web_thiserror::test_util::RecordingTransform;
+/// use actix_web_thiserror::{with_transform, ResponseError};
+/// use thiserror::Error;
+///
+/// #[derive(Debug, Error, ResponseError)]
+/// pub enum UpdateError {
+///   #[response(status = 409)]
+///   #[error("version mismatch")]
+///   VersionMismatch,
+/// }
+///
+/// let recorder = RecordingTransform::new();
+/// with_transform(recorder.clone(), || UpdateError::VersionMismatch.error_response());
+///
+/// assert_eq!(recorder.calls()[0].variant, "VersionMismatch");
+/// ```
+#[derive(Clone, Default)]
+pub struct RecordingTransform {
+  inner: Option<Arc<dyn ResponseTransform + Sync + Send>>,
+  calls: Arc<Mutex<Vec<RecordedCall>>>,
+}
+
+impl RecordingTransform {
+  /// Creates a transform responding with empty bodies.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Creates a transform responding like `inner`.
+  pub fn wrapping(inner: impl ResponseTransform + Sync + Send + 'static) -> Self {
+    Self {
+      inner: Some(Arc::new(inner)),
+      calls: Default::default(),
+    }
+  }
+
+  /// The calls recorded so far.
+  pub fn calls(&self) -> Vec<RecordedCall> {
+    self.calls.lock().unwrap().to_owned()
+  }
+
+  /// Takes the calls recorded so far, leaving none.
+  pub fn take(&self) -> Vec<RecordedCall> {
+    std::mem::take(&mut *self.calls.lock().unwrap())
+  }
+}
+
+impl ResponseTransform for RecordingTransform {
+  fn transform(
+    &self,
+    name: &str,
+    err: &dyn std::error::Error,
+    status_code: StatusCode,
+    reason: Option<serde_json::Value>,
+    _type: Option<String>,
+    details: Option<serde_json::Value>,
+  ) -> HttpResponse {
+    self.transform_with_context(&ErrorContext::from_parts(
+      name,
+      err,
+      status_code,
+      reason,
+      _type,
+      details,
+    ))
+  }
+
+  fn transform_with_context(&self, context: &ErrorContext) -> HttpResponse {
+    self.calls.lock().unwrap().push(RecordedCall::new(context));
+
+    match &self.inner {
+      Some(inner) => inner.transform_with_context(context),
+      None => HttpResponse::build(context.status_code).finish(),
+    }
+  }
+
+  fn default_error_status_code(&self) -> StatusCode {
+    match &self.inner {
+      Some(inner) => inner.default_error_status_code(),
+      None => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+  }
+}
+
+/// Renders an error with the transform in effect, returning the status code
+/// and the body of the response.
+///
+/// An empty body is `null` and a body that is not JSON is a string. Streaming
+/// bodies are not supported.
+pub fn render<E: ResponseError + ?Sized>(err: &E) -> (StatusCode, Value) {
+  let response = err.error_response();
+
+  (response.status(), body_value(response))
+}
+
+fn body_value(response: HttpResponse) -> Value {
+  let bytes = match response.into_body().try_into_bytes() {
+    Ok(bytes) => bytes,
+    Err(_) => panic!("the body of the response is streamed"),
+  };
+
+  match bytes.is_empty() {
+    true => Value::Null,
+    false => serde_json::from_slice(&bytes)
+      .unwrap_or_else(|_| Value::String(String::from_utf8_lossy(&bytes).into_owned())),
+  }
+}
+
+/// Renders an error like [`render`], along with the context it was rendered
+/// with.
+#[doc(hidden)]
+pub fn inspect<E: ResponseError + ?Sized>(err: &E) -> (StatusCode, Value, RecordedCall) {
+  let previous = CAPTURED.with(|captured| captured.borrow_mut().replace(vec![]));
+  let response = err.error_response();
+  let calls = CAPTURED.with(|captured| std::mem::replace(&mut *captured.borrow_mut(), previous));
+
+  let call = calls
+    .and_then(|mut calls| calls.pop())
+    .expect("the error was not rendered by this crate");
+
+  (response.status(), body_value(response), call)
+}
+
+/// Records the context of an error rendered while [`inspect`] runs.
+pub(crate) fn capture(context: &ErrorContext) {
+  CAPTURED.with(|captured| {
+    if let Some(calls) = captured.borrow_mut().as_mut() {
+      calls.push(RecordedCall::new(context));
+    }
+  });
+}
+
+#[doc(hidden)]
+pub fn to_value(value: &impl Serialize) -> Value {
+  serde_json::to_value(value).expect("the value failed to serialize")
+}
+
+/// Asserts the response of an error rendered by the transform in effect.
+///
+/// `status` and `body` are compared with the response, while `reason`,
+/// `_type`, `details` and `public_message` are compared with what the error
+/// was rendered with, whatever the shape of the body.
+///
+/// ```rust
+/// use actix_web::http::StatusCode;
+/// use actix_web_thiserror::{assert_response, ApiError};
+/// use serde_json::json;
+///
+/// let err = ApiError::new(StatusCode::CONFLICT)
+///   .reason("VERSION_MISMATCH")
+///   .details(json!({ "expected": 3 }));
+///
+/// assert_response!(
+///   err,
+///   status = 409,
+///   reason = "VERSION_MISMATCH",
+///   details = json!({ "expected": 3 }),
+/// );
+/// ```
+#[macro_export]
+macro_rules! assert_response {
+  ($err:expr $(, $key:ident = $value:expr)* $(,)?) => {{
+    let (status_code, body, call) = $crate::test_util::inspect(&$err);
+    $($crate::__assert_response_field!(status_code, body, call, $key, $value);)*
+  }};
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __assert_response_field {
+  ($status_code:ident, $body:ident, $call:ident, status, $value:expr) => {
+    assert_eq!($status_code, $value, "the status code of the response");
+  };
+  ($status_code:ident, $body:ident, $call:ident, body, $value:expr) => {
+    assert_eq!(
+      $body,
+      $crate::test_util::to_value(&$value),
+      "the body of the response"
+    );
+  };
+  ($status_code:ident, $body:ident, $call:ident, $key:ident, $value:expr) => {
+    assert_eq!(
+      $crate::test_util::to_value(&$call.$key),
+      $crate::test_util::to_value(&::std::option::Option::Some($value)),
+      concat!("the `", stringify!($key), "` of the error"),
+    );
+  };
+}
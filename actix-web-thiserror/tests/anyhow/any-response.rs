@@ -0,0 +1,184 @@
+use std::sync::Mutex;
+
+use actix_web::http::StatusCode;
+use actix_web::body::MessageBody as _;
+use actix_web::ResponseError as _;
+use actix_web_thiserror::registry::{find_registered, RegisteredError};
+use actix_web_thiserror::{
+  AnyResponse, ErrorContext, MetaValue, ResponseError, ResponseMeta, ResponseTransform,
+  ThiserrorResponse,
+};
+use anyhow::Context as _;
+use serde_json::json;
+use thiserror::Error;
+
+type Recorded = (String, String, u16, Option<serde_json::Value>);
+
+static RESPONSES: Mutex<Vec<Recorded>> = Mutex::new(vec![]);
+static REDACTED: Mutex<Vec<Option<String>>> = Mutex::new(vec![]);
+
+struct RecordingTransform;
+
+impl ResponseTransform for RecordingTransform {
+  fn transform_with_context(&self, context: &ErrorContext) -> actix_web::HttpResponse {
+    RESPONSES.lock().unwrap().push((
+      context.name.to_owned(),
+      context.variant.to_owned(),
+      context.status_code.as_u16(),
+      context.reason.to_owned(),
+    ));
+    REDACTED
+      .lock()
+      .unwrap()
+      .push(context.redacted_display.to_owned());
+
+    actix_web::HttpResponse::build(context.status_code).finish()
+  }
+}
+
+#[derive(Debug, Error, ResponseError)]
+enum UserError {
+  #[response(status = 404, reason = "USER_NOT_FOUND")]
+  #[error("user not found")]
+  NotFound,
+}
+
+#[derive(Debug, Error, ResponseError)]
+enum ProfileError {
+  #[response(reason = "PROFILE_UNAVAILABLE")]
+  #[error("profile unavailable")]
+  Unavailable(#[source] UserError),
+}
+
+struct TeapotTransform;
+
+impl ResponseTransform for TeapotTransform {
+  fn transform_with_context(&self, context: &ErrorContext) -> actix_web::HttpResponse {
+    actix_web::HttpResponse::build(context.status_code).body("TEAPOT")
+  }
+}
+
+#[derive(Debug, Error, ResponseError)]
+#[response(transform = TeapotTransform)]
+enum BrewError {
+  #[response(status = 418)]
+  #[error("out of tea")]
+  OutOfTea,
+}
+
+#[derive(Debug, Error, ResponseError)]
+enum LoginError {
+  #[response(status = 401)]
+  #[error("invalid login for {email} hint {hint}")]
+  InvalidLogin {
+    email: String,
+    #[response(sensitive)]
+    hint: String,
+  },
+}
+
+#[derive(Debug, Error)]
+#[error("rate limited")]
+struct RateLimited;
+
+impl ThiserrorResponse for RateLimited {
+  fn response_meta(&self) -> ResponseMeta {
+    ResponseMeta::new()
+      .status_code(StatusCode::TOO_MANY_REQUESTS)
+      .reason("RATE_LIMITED")
+  }
+}
+
+actix_web_thiserror::register_response!(RateLimited);
+
+fn main() {
+  actix_web_thiserror::set_global_transform(RecordingTransform);
+
+  // The first registered error of the chain is used.
+  let err = AnyResponse::from(
+    anyhow::Error::new(UserError::NotFound).context("loading the profile"),
+  );
+  assert_eq!(err.status_code(), StatusCode::NOT_FOUND);
+  assert_eq!(err.error_response().status(), StatusCode::NOT_FOUND);
+
+  let err = AnyResponse::from(ProfileError::Unavailable(UserError::NotFound));
+  assert_eq!(
+    err.status_code(),
+    StatusCode::INTERNAL_SERVER_ERROR
+  );
+  let _ = err.error_response();
+
+  let err: AnyResponse = Err::<(), _>(RateLimited)
+    .context("calling the upstream")
+    .unwrap_err()
+    .into();
+  let _ = err.error_response();
+
+  let _ = AnyResponse::from(anyhow::anyhow!("unexpected")).error_response();
+
+  assert_eq!(
+    std::mem::take(&mut *RESPONSES.lock().unwrap()),
+    vec![
+      (
+        "UserError".to_owned(),
+        "NotFound".to_owned(),
+        404,
+        Some(json!("USER_NOT_FOUND"))
+      ),
+      (
+        "ProfileError".to_owned(),
+        "Unavailable".to_owned(),
+        500,
+        Some(json!("PROFILE_UNAVAILABLE"))
+      ),
+      (
+        "RateLimited".to_owned(),
+        String::new(),
+        429,
+        Some(json!("RATE_LIMITED"))
+      ),
+      ("anyhow::Error".to_owned(), String::new(), 500, None),
+    ]
+  );
+
+  // Registered errors render themselves, with their own transform and
+  // sensitive fields.
+  let response = AnyResponse::from(BrewError::OutOfTea).error_response();
+  assert_eq!(response.status(), StatusCode::IM_A_TEAPOT);
+  assert_eq!(
+    response.into_body().try_into_bytes().unwrap(),
+    "TEAPOT".as_bytes()
+  );
+  assert!(RESPONSES.lock().unwrap().is_empty());
+
+  REDACTED.lock().unwrap().clear();
+  let err = AnyResponse::from(
+    anyhow::Error::new(LoginError::InvalidLogin {
+      email: "a@b".to_owned(),
+      hint: "hunter2".to_owned(),
+    })
+    .context("logging in"),
+  );
+  let _ = err.error_response();
+
+  assert_eq!(
+    std::mem::take(&mut *REDACTED.lock().unwrap()),
+    vec![Some("invalid login for a@b hint [REDACTED]".to_owned())]
+  );
+
+  // Boxed errors are searched the same way.
+  let boxed: Box<dyn std::error::Error + Send + Sync> = Box::new(UserError::NotFound);
+  let registered = find_registered(boxed.as_ref()).unwrap();
+
+  assert_eq!(registered.name, "UserError");
+  assert_eq!(registered.meta.reason, MetaValue::Set(json!("USER_NOT_FOUND")));
+
+  // So are actix errors created from an `AnyResponse`.
+  let err = actix_web::Error::from(AnyResponse::from(
+    anyhow::Error::new(UserError::NotFound).context("loading the profile"),
+  ));
+  let registered = RegisteredError::from_actix_error(&err).unwrap();
+
+  assert_eq!(registered.variant, "NotFound");
+  assert_eq!(registered.status_code, StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,30 @@
+use actix_web_thiserror::ResponseError;
+use thiserror::Error;
+
+#[derive(Debug, Error, ResponseError)]
+enum UserError {
+  #[response(status = 404, reason = "USER_NOT_FOUND")]
+  #[error("user not found")]
+  NotFound,
+}
+
+#[derive(Debug, Error, ResponseError)]
+enum GenericError<T: std::fmt::Debug> {
+  #[response(status = 400, reason = "INVALID")]
+  #[error("invalid: {0:?}")]
+  Invalid(T),
+}
+
+fn main() {
+  let catalogs = actix_web_thiserror::error_catalogs();
+  let names = catalogs
+    .iter()
+    .map(|catalog| catalog.name)
+    .collect::<Vec<_>>();
+
+  assert_eq!(names, vec!["GenericError", "UserError"]);
+  assert_eq!(catalogs[1].entries[0].variant, "NotFound");
+  assert_eq!(catalogs[1].entries[0].status, Some(404));
+
+  let _ = (UserError::NotFound, GenericError::Invalid(()));
+}
@@ -0,0 +1,8 @@
+use actix_web_thiserror::FromErrorResponse;
+
+#[derive(Debug, FromErrorResponse)]
+struct UploadError {
+  path: String,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use actix_web_thiserror::ResponseError;
+use thiserror::Error;
+
+#[derive(Debug, Error, ResponseError)]
+enum MacroErrors {
+  #[response(expose, public = "public message")]
+  #[error("private message")]
+  PublicAndExpose,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use actix_web_thiserror::ResponseError;
+use thiserror::Error;
+
+#[derive(Debug, Error, ResponseError)]
+#[response(reason_from_variant = "Title Case")]
+enum MacroErrors {
+  #[error("unknown case")]
+  UnknownCase,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use actix_web_thiserror::ResponseError;
+use thiserror::Error;
+
+#[derive(Debug, Error, ResponseError)]
+#[response(reason_prefix = "IMG_")]
+enum MacroErrors {
+  #[error("prefix without case")]
+  PrefixWithoutCase,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use actix_web_thiserror::ResponseError;
+use thiserror::Error;
+
+#[derive(Debug, Error, ResponseError)]
+enum MacroErrors {
+  #[error("invalid token {0}")]
+  InvalidToken(#[response(secret)] String),
+}
+
+fn main() {}
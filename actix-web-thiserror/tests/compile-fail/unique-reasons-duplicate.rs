@@ -0,0 +1,15 @@
+use actix_web_thiserror::ResponseError;
+use thiserror::Error;
+
+#[derive(Debug, Error, ResponseError)]
+#[response(unique_reasons)]
+enum MacroErrors {
+  #[response(reason = "NOT_FOUND")]
+  #[error("user not found")]
+  UserNotFound,
+  #[response(reason = "NOT_FOUND")]
+  #[error("image not found")]
+  ImageNotFound,
+}
+
+fn main() {}
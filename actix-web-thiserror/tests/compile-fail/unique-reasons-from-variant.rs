@@ -0,0 +1,14 @@
+use actix_web_thiserror::ResponseError;
+use thiserror::Error;
+
+#[derive(Debug, Error, ResponseError)]
+#[response(unique_reasons, reason_from_variant = "SCREAMING_SNAKE_CASE")]
+enum MacroErrors {
+  #[error("not found")]
+  NotFound,
+  #[response(reason = "NOT_FOUND")]
+  #[error("missing")]
+  Missing,
+}
+
+fn main() {}
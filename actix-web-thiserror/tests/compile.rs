@@ -17,3 +17,52 @@ fn pass() {
   let t = trybuild::TestCases::new();
   t.pass("tests/pass/*.rs");
 }
+
+#[cfg(feature = "catalog")]
+#[test]
+fn catalog() {
+  let t = trybuild::TestCases::new();
+  t.pass("tests/catalog/*.rs");
+}
+
+#[cfg(feature = "openapi")]
+#[test]
+fn openapi() {
+  let t = trybuild::TestCases::new();
+  t.pass("tests/openapi/*.rs");
+}
+
+#[cfg(feature = "localization")]
+#[test]
+fn localization() {
+  let t = trybuild::TestCases::new();
+  t.pass("tests/localization/*.rs");
+}
+
+#[cfg(feature = "metrics")]
+#[test]
+fn metrics() {
+  let t = trybuild::TestCases::new();
+  t.pass("tests/metrics/*.rs");
+}
+
+#[cfg(feature = "anyhow")]
+#[test]
+fn anyhow() {
+  let t = trybuild::TestCases::new();
+  t.pass("tests/anyhow/*.rs");
+}
+
+#[cfg(feature = "registry")]
+#[test]
+fn registry() {
+  let t = trybuild::TestCases::new();
+  t.pass("tests/registry/*.rs");
+}
+
+#[cfg(feature = "test-util")]
+#[test]
+fn test_util() {
+  let t = trybuild::TestCases::new();
+  t.pass("tests/test-util/*.rs");
+}
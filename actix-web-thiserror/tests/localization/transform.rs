@@ -0,0 +1,98 @@
+use actix_web::{test, web, App, HttpResponse};
+use actix_web_thiserror::localization::{Localization, LocalizingTransform};
+use actix_web_thiserror::ResponseError;
+use serde_json::json;
+use thiserror::Error;
+
+#[derive(Debug, Error, ResponseError)]
+enum ImageError {
+  #[response(status = 400, reason = "INVALID_IMAGE_FORMAT", details = "{0}")]
+  #[error("invalid image format: {0}")]
+  InvalidImageFormat(String),
+  #[response(status = 413, reason = "MISSING_KEY")]
+  #[error("image too large")]
+  TooLarge,
+}
+
+async fn invalid() -> Result<HttpResponse, actix_web::Error> {
+  Err(ImageError::InvalidImageFormat("bmp".to_owned()))?
+}
+
+async fn too_large() -> Result<HttpResponse, actix_web::Error> {
+  Err(ImageError::TooLarge)?
+}
+
+#[actix_web::main]
+async fn main() {
+  let dir = std::env::temp_dir().join(format!("actix-web-thiserror-{}", std::process::id()));
+  std::fs::create_dir_all(&dir).unwrap();
+  std::fs::write(
+    dir.join("en-US.ftl"),
+    "INVALID_IMAGE_FORMAT = The format { $details } is not supported.\n",
+  )
+  .unwrap();
+  std::fs::write(
+    dir.join("fr.json"),
+    r#"{ "INVALID_IMAGE_FORMAT": "Le format {details} n'est pas pris en charge." }"#,
+  )
+  .unwrap();
+
+  actix_web_thiserror::set_global_transform(LocalizingTransform::new("en-US").with_dir(&dir).unwrap());
+
+  let app = test::init_service(
+    App::new()
+      .wrap(Localization)
+      .route("/invalid", web::get().to(invalid))
+      .route("/too-large", web::get().to(too_large)),
+  )
+  .await;
+
+  let cases = [
+    (
+      "/invalid",
+      "fr-CH, en;q=0.5",
+      "fr",
+      json!({ "reason": "INVALID_IMAGE_FORMAT", "message": "Le format bmp n'est pas pris en charge." }),
+    ),
+    (
+      "/invalid",
+      "de",
+      "en-US",
+      json!({ "reason": "INVALID_IMAGE_FORMAT", "message": "The format bmp is not supported." }),
+    ),
+    (
+      "/too-large",
+      "en",
+      "en-US",
+      json!({ "reason": "MISSING_KEY", "message": "Payload Too Large" }),
+    ),
+  ];
+
+  for (uri, accept_language, locale, body) in cases {
+    let res = test::call_service(
+      &app,
+      test::TestRequest::get()
+        .uri(uri)
+        .insert_header(("Accept-Language", accept_language))
+        .to_request(),
+    )
+    .await;
+
+    assert_eq!(res.headers().get("Content-Language").unwrap(), locale);
+    assert_eq!(test::read_body_json::<serde_json::Value, _>(res).await, body);
+  }
+
+  // The fallback on the primary language does not depend on hashing.
+  for _ in 0..16 {
+    let transform = LocalizingTransform::new("fr")
+      .with_json("en-US", &dir.join("fr.json"))
+      .unwrap()
+      .with_json("en-GB", &dir.join("fr.json"))
+      .unwrap();
+
+    assert_eq!(transform.negotiate(&["en".to_owned()]), "en-GB");
+    assert_eq!(transform.negotiate(&["en-us".to_owned()]), "en-US");
+  }
+
+  std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,106 @@
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use actix_web::ResponseError as _;
+use actix_web_thiserror::metrics::ERRORS_TOTAL;
+use actix_web_thiserror::ResponseError;
+use metrics::{
+  Counter, CounterFn, Gauge, Histogram, Key, KeyName, Metadata, Recorder, SharedString, Unit,
+};
+use thiserror::Error;
+
+#[derive(Default)]
+struct TestCounter(AtomicU64);
+
+impl CounterFn for TestCounter {
+  fn increment(&self, value: u64) {
+    self.0.fetch_add(value, Ordering::Relaxed);
+  }
+
+  fn absolute(&self, value: u64) {
+    self.0.store(value, Ordering::Relaxed);
+  }
+}
+
+#[derive(Default)]
+struct TestRecorder {
+  counters: Mutex<BTreeMap<String, Arc<TestCounter>>>,
+}
+
+impl Recorder for TestRecorder {
+  fn describe_counter(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+  fn describe_gauge(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+  fn describe_histogram(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+  fn register_counter(&self, key: &Key, _: &Metadata<'_>) -> Counter {
+    let labels = key
+      .labels()
+      .map(|label| format!("{}={}", label.key(), label.value()))
+      .collect::<Vec<_>>()
+      .join(",");
+
+    Counter::from_arc(
+      self
+        .counters
+        .lock()
+        .unwrap()
+        .entry(format!("{}{{{}}}", key.name(), labels))
+        .or_default()
+        .to_owned(),
+    )
+  }
+
+  fn register_gauge(&self, _: &Key, _: &Metadata<'_>) -> Gauge {
+    Gauge::noop()
+  }
+
+  fn register_histogram(&self, _: &Key, _: &Metadata<'_>) -> Histogram {
+    Histogram::noop()
+  }
+}
+
+#[derive(Debug, Error, ResponseError)]
+enum UploadError {
+  #[response(status = 413, reason = "TOO_LARGE")]
+  #[error("file too large")]
+  TooLarge,
+  #[error("storage unavailable")]
+  Storage,
+}
+
+fn main() {
+  let recorder: &'static TestRecorder = Box::leak(Box::default());
+  metrics::set_global_recorder(recorder).unwrap();
+
+  for err in [UploadError::TooLarge, UploadError::TooLarge, UploadError::Storage] {
+    let _ = err.error_response();
+  }
+
+  let counters = recorder
+    .counters
+    .lock()
+    .unwrap()
+    .iter()
+    .map(|(key, counter)| (key.to_owned(), counter.0.load(Ordering::Relaxed)))
+    .collect::<Vec<_>>();
+
+  assert_eq!(
+    counters,
+    vec![
+      (
+        format!("{}{{error=UploadError,variant=Storage,status=500,reason=}}", ERRORS_TOTAL),
+        1
+      ),
+      (
+        format!(
+          "{}{{error=UploadError,variant=TooLarge,status=413,reason=TOO_LARGE}}",
+          ERRORS_TOTAL
+        ),
+        2
+      ),
+    ]
+  );
+}
@@ -0,0 +1,251 @@
+use actix_web::http::StatusCode;
+use actix_web_thiserror::{
+  BodyTransform, ErrorCatalogEntry, ErrorContext, LegacyResultTransform, ResponseError,
+  ResponseTransform,
+};
+use serde_json::json;
+use thiserror::Error;
+use utoipa::openapi::schema::{ObjectBuilder, Schema, Type};
+use utoipa::openapi::RefOr;
+use utoipa::IntoResponses;
+
+struct ReasonTransform;
+
+impl ResponseTransform for ReasonTransform {
+  fn openapi_schema(&self) -> Option<RefOr<Schema>> {
+    Some(
+      ObjectBuilder::new()
+        .property("reason", ObjectBuilder::new().schema_type(Type::String))
+        .into(),
+    )
+  }
+
+  fn openapi_example(&self, entry: &ErrorCatalogEntry) -> Option<serde_json::Value> {
+    Some(json!({ "reason": entry.reason }))
+  }
+}
+
+struct CodeTransform;
+
+impl BodyTransform for CodeTransform {
+  fn body(&self, context: &ErrorContext) -> impl serde::Serialize {
+    json!({ "code": context.reason })
+  }
+
+  fn openapi_schema(&self) -> Option<RefOr<Schema>> {
+    Some(
+      ObjectBuilder::new()
+        .property("code", ObjectBuilder::new().schema_type(Type::String))
+        .into(),
+    )
+  }
+
+  fn openapi_example(&self, entry: &ErrorCatalogEntry) -> Option<serde_json::Value> {
+    Some(json!({ "code": entry.reason }))
+  }
+}
+
+struct GatewayTransform;
+
+impl ResponseTransform for GatewayTransform {
+  fn default_error_status_code(&self) -> StatusCode {
+    StatusCode::BAD_GATEWAY
+  }
+
+  fn openapi_schema(&self) -> Option<RefOr<Schema>> {
+    Some(
+      ObjectBuilder::new()
+        .property("gateway", ObjectBuilder::new().schema_type(Type::String))
+        .into(),
+    )
+  }
+
+  fn openapi_example(&self, entry: &ErrorCatalogEntry) -> Option<serde_json::Value> {
+    Some(json!({ "gateway": entry.variant }))
+  }
+}
+
+#[derive(Debug, Error, ResponseError)]
+#[response(transform = GatewayTransform)]
+enum UpstreamError {
+  #[error("upstream down")]
+  Down,
+  #[response(status = 404, reason = "UPSTREAM_MISSING", transform = ReasonTransform)]
+  #[error("upstream missing")]
+  Missing,
+}
+
+#[derive(Debug, Error, ResponseError)]
+enum Inner {
+  #[response(status = 404, reason = "NOT_FOUND")]
+  #[error("inner not found")]
+  NotFound,
+}
+
+#[derive(Debug, Error, ResponseError)]
+enum ImageError {
+  #[response(status = 400, reason = "INVALID_IMAGE_FORMAT")]
+  #[error("invalid image format")]
+  InvalidImageFormat,
+  #[response(status = 404, reason = "IMAGE_NOT_FOUND")]
+  #[error("image not found")]
+  NotFound,
+  #[error("unknown error")]
+  Unknown,
+  #[response(forward)]
+  #[error(transparent)]
+  Inner(Inner),
+}
+
+// Sources that do not implement `ThiserrorResponse` are documented as any
+// other variant.
+#[derive(Debug, Error, ResponseError)]
+#[response(auto_forward)]
+enum FetchError {
+  #[error(transparent)]
+  Inner(#[from] Inner),
+  #[error("i/o error")]
+  Io(#[from] std::io::Error),
+}
+
+fn main() {
+  // Enums and variants with a transform of their own are documented with it.
+  let responses = serde_json::to_value(UpstreamError::responses()).unwrap();
+
+  assert_eq!(
+    responses,
+    json!({
+      "404": {
+        "description": "- `UPSTREAM_MISSING`: upstream missing",
+        "content": {
+          "application/json": {
+            "schema": {
+              "type": "object",
+              "properties": { "reason": { "type": "string" } },
+            },
+            "examples": {
+              "UpstreamError::Missing": {
+                "summary": "upstream missing",
+                "value": { "reason": "UPSTREAM_MISSING" },
+              },
+            },
+          },
+        },
+      },
+      "502": {
+        "description": "- `Down`: upstream down",
+        "content": {
+          "application/json": {
+            "schema": {
+              "type": "object",
+              "properties": { "gateway": { "type": "string" } },
+            },
+            "examples": {
+              "UpstreamError::Down": {
+                "summary": "upstream down",
+                "value": { "gateway": "Down" },
+              },
+            },
+          },
+        },
+      },
+    })
+  );
+
+  let responses = serde_json::to_value(ImageError::responses()).unwrap();
+
+  assert_eq!(
+    responses,
+    json!({
+      "400": { "description": "- `INVALID_IMAGE_FORMAT`: invalid image format" },
+      "404": { "description": "- `IMAGE_NOT_FOUND`: image not found\n- `NOT_FOUND`: inner not found" },
+      "500": { "description": "- `Unknown`: unknown error" },
+    })
+  );
+
+  let responses = serde_json::to_value(FetchError::responses()).unwrap();
+
+  assert_eq!(
+    responses,
+    json!({
+      "404": { "description": "- `NOT_FOUND`: inner not found" },
+      "500": { "description": "- `Io`: i/o error" },
+    })
+  );
+
+  actix_web_thiserror::set_global_transform(ReasonTransform);
+
+  let responses = serde_json::to_value(ImageError::responses()).unwrap();
+
+  assert_eq!(
+    responses["404"]["content"]["application/json"],
+    json!({
+      "schema": {
+        "type": "object",
+        "properties": { "reason": { "type": "string" } },
+      },
+      "examples": {
+        "ImageError::NotFound": {
+          "summary": "image not found",
+          "value": { "reason": "IMAGE_NOT_FOUND" },
+        },
+        "Inner::NotFound": {
+          "summary": "inner not found",
+          "value": { "reason": "NOT_FOUND" },
+        },
+      },
+    })
+  );
+  actix_web_thiserror::set_global_transform(CodeTransform);
+
+  let responses = serde_json::to_value(ImageError::responses()).unwrap();
+
+  assert_eq!(
+    responses["400"]["content"]["application/json"],
+    json!({
+      "schema": {
+        "type": "object",
+        "properties": { "code": { "type": "string" } },
+      },
+      "examples": {
+        "ImageError::InvalidImageFormat": {
+          "summary": "invalid image format",
+          "value": { "code": "INVALID_IMAGE_FORMAT" },
+        },
+      },
+    })
+  );
+  actix_web_thiserror::set_global_transform(
+    LegacyResultTransform::new()
+      .result("success", false)
+      .reason_key("code")
+      .type_key("kind"),
+  );
+
+  let responses = serde_json::to_value(ImageError::responses()).unwrap();
+
+  assert_eq!(
+    responses["404"]["content"]["application/json"],
+    json!({
+      "schema": {
+        "type": "object",
+        "properties": {
+          "success": { "type": "boolean" },
+          "code": {},
+          "kind": { "type": "string" },
+        },
+        "required": ["success", "code"],
+      },
+      "examples": {
+        "ImageError::NotFound": {
+          "summary": "image not found",
+          "value": { "success": false, "code": "IMAGE_NOT_FOUND" },
+        },
+        "Inner::NotFound": {
+          "summary": "inner not found",
+          "value": { "success": false, "code": "NOT_FOUND" },
+        },
+      },
+    })
+  );
+}
@@ -0,0 +1,104 @@
+use std::sync::Mutex;
+
+use actix_web::http::StatusCode;
+use actix_web::ResponseError as _;
+use actix_web_thiserror::{ApiError, ErrorContext, ResponseError, ResponseTransform};
+use serde_json::json;
+use thiserror::Error;
+
+type Recorded = (
+  String,
+  u16,
+  Option<serde_json::Value>,
+  Option<String>,
+  Option<serde_json::Value>,
+);
+
+static RESPONSES: Mutex<Vec<Recorded>> = Mutex::new(vec![]);
+
+struct RecordingTransform;
+
+impl ResponseTransform for RecordingTransform {
+  fn transform_with_context(&self, context: &ErrorContext) -> actix_web::HttpResponse {
+    RESPONSES.lock().unwrap().push((
+      context.name.to_owned(),
+      context.status_code.as_u16(),
+      context.reason.to_owned(),
+      context._type.to_owned(),
+      context.details.to_owned(),
+    ));
+
+    actix_web::HttpResponse::build(context.status_code).finish()
+  }
+}
+
+#[derive(Debug, Error, ResponseError)]
+enum UpdateError {
+  #[response(status = 404, reason = "NOT_FOUND")]
+  #[error("document not found")]
+  NotFound,
+  #[response(forward)]
+  #[error(transparent)]
+  Other(ApiError),
+}
+
+fn main() {
+  actix_web_thiserror::set_global_transform(RecordingTransform);
+
+  let conflict = ApiError::new(StatusCode::CONFLICT)
+    .reason("VERSION_MISMATCH")
+    ._type("conflict")
+    .details(json!({ "expected": 3, "found": 2 }));
+
+  assert_eq!(conflict.to_string(), "Conflict");
+  assert_eq!(
+    ApiError::new(StatusCode::CONFLICT)
+      .message("version 2 is outdated")
+      .to_string(),
+    "version 2 is outdated"
+  );
+
+  let response = conflict.error_response();
+  assert_eq!(response.status(), StatusCode::CONFLICT);
+
+  let response = UpdateError::Other(conflict).error_response();
+  assert_eq!(response.status(), StatusCode::CONFLICT);
+
+  let _ = UpdateError::NotFound.error_response();
+  let _ = ApiError::new(StatusCode::TOO_MANY_REQUESTS).error_response();
+
+  let conflict = (
+    409,
+    Some(json!("VERSION_MISMATCH")),
+    Some("conflict".to_owned()),
+    Some(json!({ "expected": 3, "found": 2 })),
+  );
+
+  assert_eq!(
+    std::mem::take(&mut *RESPONSES.lock().unwrap()),
+    vec![
+      (
+        "ApiError".to_owned(),
+        conflict.0,
+        conflict.1.to_owned(),
+        conflict.2.to_owned(),
+        conflict.3.to_owned(),
+      ),
+      (
+        "UpdateError".to_owned(),
+        conflict.0,
+        conflict.1,
+        conflict.2,
+        conflict.3,
+      ),
+      (
+        "UpdateError".to_owned(),
+        404,
+        Some(json!("NOT_FOUND")),
+        None,
+        None
+      ),
+      ("ApiError".to_owned(), 429, None, None, None),
+    ]
+  );
+}
@@ -0,0 +1,142 @@
+use std::sync::Mutex;
+
+use actix_web::ResponseError as _;
+use actix_web_thiserror::{ErrorContext, ResponseError, ResponseTransform, ThiserrorCatalog};
+use thiserror::Error;
+
+static RESPONSES: Mutex<Vec<(u16, Option<serde_json::Value>)>> = Mutex::new(vec![]);
+
+struct RecordingTransform;
+
+impl ResponseTransform for RecordingTransform {
+  fn transform_with_context(&self, context: &ErrorContext) -> actix_web::HttpResponse {
+    RESPONSES
+      .lock()
+      .unwrap()
+      .push((context.status_code.as_u16(), context.reason.to_owned()));
+
+    actix_web::HttpResponse::build(context.status_code).finish()
+  }
+}
+
+#[derive(Debug, Error, ResponseError)]
+enum StorageError {
+  #[response(status = 503, reason = "STORAGE_UNAVAILABLE")]
+  #[error("storage unavailable")]
+  Unavailable,
+}
+
+#[derive(Debug, Error, ResponseError)]
+#[response(auto_forward)]
+enum UploadError {
+  #[error(transparent)]
+  Transparent(StorageError),
+  #[error("upload failed")]
+  From(#[from] StorageError),
+  #[error("could not store {path}")]
+  Source {
+    path: String,
+    #[source]
+    err: StorageError,
+  },
+  #[error("could not read {0}")]
+  ImplicitSource(String, #[source] StorageError),
+  // Explicit options take precedence over the forwarded ones.
+  #[response(status = 400)]
+  #[error("invalid upload")]
+  Overridden(#[source] StorageError),
+  #[response(status = 413, reason = "TOO_LARGE")]
+  #[error("file too large")]
+  TooLarge,
+  // Sources that are not response errors forward nothing.
+  #[error("i/o error")]
+  Io(#[from] std::io::Error),
+}
+
+// Generic fields are bounded on `ThiserrorResponse`, as specialization cannot
+// see through them.
+#[derive(Debug, Error, ResponseError)]
+#[response(auto_forward)]
+enum WrappedError<T: std::error::Error + 'static> {
+  #[error(transparent)]
+  Wrapped(#[from] T),
+}
+
+// Variants whose field does not implement `ThiserrorResponse` are not
+// forwarded, so they get a reason from their name.
+#[derive(Debug, Error, ResponseError)]
+#[response(auto_forward, reason_from_variant = "SCREAMING_SNAKE_CASE")]
+enum ArchiveError {
+  #[error(transparent)]
+  Storage(#[from] StorageError),
+  #[error("i/o error")]
+  Io(#[from] std::io::Error),
+}
+
+#[derive(Debug, Error, ResponseError)]
+enum ExplicitError {
+  // Without `auto_forward`, thiserror attributes are ignored.
+  #[error(transparent)]
+  Transparent(StorageError),
+}
+
+fn main() {
+  actix_web_thiserror::set_global_transform(RecordingTransform);
+
+  let errors = [
+    UploadError::Transparent(StorageError::Unavailable),
+    UploadError::From(StorageError::Unavailable),
+    UploadError::Source {
+      path: "a.png".to_owned(),
+      err: StorageError::Unavailable,
+    },
+    UploadError::ImplicitSource("a.png".to_owned(), StorageError::Unavailable),
+    UploadError::Overridden(StorageError::Unavailable),
+    UploadError::TooLarge,
+    UploadError::Io(std::io::Error::other("disk full")),
+  ];
+
+  for err in &errors {
+    let _ = err.error_response();
+  }
+
+  let _ = ExplicitError::Transparent(StorageError::Unavailable).error_response();
+  let _ = WrappedError::from(StorageError::Unavailable).error_response();
+  let _ = ArchiveError::from(StorageError::Unavailable).error_response();
+  let _ = ArchiveError::from(std::io::Error::other("disk full")).error_response();
+
+  let unavailable = (503, Some(serde_json::json!("STORAGE_UNAVAILABLE")));
+
+  assert_eq!(
+    std::mem::take(&mut *RESPONSES.lock().unwrap()),
+    vec![
+      unavailable.to_owned(),
+      unavailable.to_owned(),
+      unavailable.to_owned(),
+      unavailable.to_owned(),
+      (400, Some(serde_json::json!("STORAGE_UNAVAILABLE"))),
+      (413, Some(serde_json::json!("TOO_LARGE"))),
+      (500, None),
+      (500, None),
+      unavailable.to_owned(),
+      unavailable.to_owned(),
+      (500, Some(serde_json::json!("IO"))),
+    ]
+  );
+  assert_eq!(
+    UploadError::error_catalog()
+      .entries
+      .iter()
+      .map(|entry| entry.forward)
+      .collect::<Vec<_>>(),
+    vec![true, true, true, true, true, false, false]
+  );
+  assert_eq!(
+    ArchiveError::error_catalog()
+      .entries
+      .iter()
+      .map(|entry| (entry.forward, entry.reason.to_owned()))
+      .collect::<Vec<_>>(),
+    vec![(true, None), (false, Some(serde_json::json!("IO")))]
+  );
+}
@@ -0,0 +1,121 @@
+use actix_web::body::MessageBody as _;
+use actix_web::http::StatusCode;
+use actix_web::ResponseError as _;
+use actix_web_thiserror::{
+  with_transform, BodyTransform, ErrorContext, JsonTransform, ResponseError, ResponseTransform,
+  ResponseTransformExt as _,
+};
+use serde::Serialize;
+use serde_json::json;
+use thiserror::Error;
+
+#[derive(Serialize)]
+struct Problem {
+  title: String,
+  status: u16,
+}
+
+struct ProblemTransform;
+
+impl BodyTransform for ProblemTransform {
+  fn body(&self, context: &ErrorContext) -> impl Serialize {
+    Problem {
+      title: context.variant.to_owned(),
+      status: context.status_code.as_u16(),
+    }
+  }
+
+  fn default_error_status_code(&self) -> StatusCode {
+    StatusCode::BAD_GATEWAY
+  }
+}
+
+#[derive(Debug, Error, ResponseError)]
+enum Base64ImageError {
+  #[response(reason = "INVALID_IMAGE_FORMAT")]
+  #[error("invalid image format")]
+  InvalidImageFormat,
+  #[response(status = 413, reason = "TOO_LARGE", type = "image", details = "{0}")]
+  #[error("image too large")]
+  TooLarge(usize),
+  #[response(reason = "UNAVAILABLE", expose)]
+  #[error("storage unavailable")]
+  Unavailable,
+}
+
+fn render(err: Base64ImageError) -> (StatusCode, String, serde_json::Value) {
+  let response = err.error_response();
+  let status = response.status();
+  let content_type = response.headers().get("content-type").unwrap().to_str().unwrap().to_owned();
+  let body = response.into_body().try_into_bytes().unwrap();
+
+  (status, content_type, serde_json::from_slice(&body).unwrap())
+}
+
+fn main() {
+  with_transform(JsonTransform, || {
+    assert_eq!(
+      render(Base64ImageError::InvalidImageFormat),
+      (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        "application/json".to_owned(),
+        json!({ "result": 0, "reason": "INVALID_IMAGE_FORMAT" }),
+      )
+    );
+    assert_eq!(
+      render(Base64ImageError::TooLarge(10)).2,
+      json!({ "result": 0, "reason": "TOO_LARGE", "type": "image", "details": 10 })
+    );
+    assert_eq!(
+      render(Base64ImageError::Unavailable).2,
+      json!({ "result": 0, "reason": "UNAVAILABLE", "message": "storage unavailable" })
+    );
+  });
+
+  with_transform(ProblemTransform, || {
+    assert_eq!(
+      render(Base64ImageError::InvalidImageFormat),
+      (
+        StatusCode::BAD_GATEWAY,
+        "application/json".to_owned(),
+        json!({ "title": "InvalidImageFormat", "status": 502 }),
+      )
+    );
+  });
+
+  // `transform` goes through `transform_with_context` as well.
+  let transform = |transform: &dyn ResponseTransform| {
+    let response = transform.transform(
+      "Base64ImageError",
+      &Base64ImageError::InvalidImageFormat,
+      StatusCode::BAD_REQUEST,
+      Some(json!("INVALID_IMAGE_FORMAT")),
+      None,
+      None,
+    );
+    let body = response.into_body().try_into_bytes().unwrap();
+
+    serde_json::from_slice::<serde_json::Value>(&body).unwrap()
+  };
+  let expected = json!({ "result": 0, "reason": "INVALID_IMAGE_FORMAT" });
+
+  assert_eq!(transform(&JsonTransform), expected);
+  assert_eq!(
+    transform(&JsonTransform.map_response(|response| response)),
+    expected
+  );
+
+  with_transform(JsonTransform, || {
+    let response = actix_web_thiserror::apply_global_transform(
+      "Base64ImageError",
+      &Base64ImageError::InvalidImageFormat,
+      StatusCode::BAD_REQUEST,
+      Some(json!("INVALID_IMAGE_FORMAT")),
+      None,
+      None,
+    );
+    let body = response.into_body().try_into_bytes().unwrap();
+
+    assert_eq!(serde_json::from_slice::<serde_json::Value>(&body).unwrap(), expected);
+  });
+}
@@ -0,0 +1,104 @@
+use actix_web_thiserror::{ResponseError, ThiserrorCatalog};
+use serde_json::json;
+use thiserror::Error;
+
+#[derive(Debug, Error, ResponseError)]
+enum Inner {
+  #[response(status = 404, reason = "NOT_FOUND")]
+  #[error("inner not found")]
+  NotFound,
+}
+
+#[derive(Debug, Error, ResponseError)]
+enum ImageError {
+  #[response(status = 400, reason = "INVALID_IMAGE_FORMAT", type = "image")]
+  #[error("invalid image format")]
+  InvalidImageFormat,
+  #[response(status = 413, details = "{0}")]
+  #[error("image too large: {0} bytes")]
+  TooLarge(u64),
+  #[error("unknown | error")]
+  Unknown,
+  #[response(forward)]
+  #[error(transparent)]
+  Inner(Inner),
+}
+
+#[derive(Debug, Error, ResponseError)]
+enum LabelError {
+  #[response(status = 400, details = "{0}")]
+  #[error("invalid label")]
+  Invalid(&'static str),
+}
+
+fn main() {
+  let catalog = ImageError::error_catalog();
+
+  assert_eq!(catalog.name, "ImageError");
+  assert_eq!(
+    catalog.to_json(),
+    json!({
+      "name": "ImageError",
+      "entries": [
+        {
+          "variant": "InvalidImageFormat",
+          "status": 400,
+          "reason": "INVALID_IMAGE_FORMAT",
+          "type": "image",
+          "display": "invalid image format",
+          "details": null,
+          "details_type": null,
+          "forward": false,
+        },
+        {
+          "variant": "TooLarge",
+          "status": 413,
+          "reason": null,
+          "type": null,
+          "display": "image too large: {0} bytes",
+          "details": "{0}",
+          "details_type": "u64",
+          "forward": false,
+        },
+        {
+          "variant": "Unknown",
+          "status": null,
+          "reason": null,
+          "type": null,
+          "display": "unknown | error",
+          "details": null,
+          "details_type": null,
+          "forward": false,
+        },
+        {
+          "variant": "Inner",
+          "status": null,
+          "reason": null,
+          "type": null,
+          "display": null,
+          "details": null,
+          "details_type": null,
+          "forward": true,
+        },
+      ],
+    })
+  );
+  assert_eq!(
+    catalog.to_markdown(),
+    "## ImageError
+
+| Variant | Status | Reason | Type | Message | Details |
+| --- | --- | --- | --- | --- | --- |
+| `InvalidImageFormat` | 400 | `\"INVALID_IMAGE_FORMAT\"` | `image` | invalid image format |  |
+| `TooLarge` | 413 |  |  | image too large: {0} bytes | `{0}`: `u64` |
+| `Unknown` | 500 |  |  | unknown \\| error |  |
+| `Inner` | forwarded |  |  |  |  |
+"
+  );
+
+  // Types are written as their tokens.
+  assert_eq!(
+    LabelError::error_catalog().entries[0].details_type,
+    Some("& 'static str")
+  );
+}
@@ -0,0 +1,131 @@
+use std::sync::Mutex;
+
+use actix_web::{test, web, App};
+use actix_web_thiserror::{CatchPanic, ErrorContext, RequestId, ResponseTransform};
+use serde_json::json;
+
+type Recorded = (String, u16, Option<serde_json::Value>, String);
+
+static RESPONSES: Mutex<Vec<Recorded>> = Mutex::new(vec![]);
+static CORRELATION_IDS: Mutex<Vec<String>> = Mutex::new(vec![]);
+
+struct RecordingTransform;
+
+impl ResponseTransform for RecordingTransform {
+  fn transform_with_context(&self, context: &ErrorContext) -> actix_web::HttpResponse {
+    RESPONSES.lock().unwrap().push((
+      context.name.to_owned(),
+      context.status_code.as_u16(),
+      context.reason.to_owned(),
+      context.err.to_string(),
+    ));
+    CORRELATION_IDS
+      .lock()
+      .unwrap()
+      .push(context.correlation_id.to_owned());
+
+    actix_web::HttpResponse::build(context.status_code).finish()
+  }
+}
+
+async fn ok() -> &'static str {
+  "ok"
+}
+
+async fn panics() -> &'static str {
+  panic!("boom")
+}
+
+async fn panics_with_string(path: web::Path<u32>) -> &'static str {
+  panic!("boom {}", path.into_inner())
+}
+
+fn main() {
+  actix_web_thiserror::set_global_transform(RecordingTransform);
+  std::panic::set_hook(Box::new(|_| {}));
+
+  let statuses = actix_web::rt::System::new().block_on(async {
+    let app = test::init_service(
+      App::new()
+        .wrap(CatchPanic::new())
+        .route("/ok", web::get().to(ok))
+        .route("/panic", web::get().to(panics))
+        .route("/panic/{id}", web::get().to(panics_with_string)),
+    )
+    .await;
+
+    let custom = test::init_service(
+      App::new()
+        .wrap(CatchPanic::new().reason("INTERNAL_PANIC"))
+        .route("/panic", web::get().to(panics)),
+    )
+    .await;
+
+    let mut statuses = vec![];
+
+    for uri in ["/ok", "/panic", "/panic/7"] {
+      let req = test::TestRequest::get().uri(uri).to_request();
+      statuses.push(match test::try_call_service(&app, req).await {
+        Ok(res) => res.status().as_u16(),
+        Err(err) => err.error_response().status().as_u16(),
+      });
+    }
+
+    let req = test::TestRequest::get().uri("/panic").to_request();
+    let err = test::try_call_service(&custom, req).await.unwrap_err();
+    statuses.push(err.error_response().status().as_u16());
+
+    // Panics are rendered within the outer middleware.
+    let correlated = test::init_service(
+      App::new()
+        .wrap(CatchPanic::new())
+        .wrap(RequestId)
+        .route("/panic", web::get().to(panics)),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+      .uri("/panic")
+      .insert_header(("x-request-id", "abc"))
+      .to_request();
+    let err = test::try_call_service(&correlated, req).await.unwrap_err();
+    statuses.push(err.error_response().status().as_u16());
+
+    statuses
+  });
+
+  assert_eq!(statuses, vec![200, 500, 500, 500, 500]);
+  assert_eq!(
+    std::mem::take(&mut *RESPONSES.lock().unwrap()),
+    vec![
+      (
+        "PanicError".to_owned(),
+        500,
+        Some(json!("PANIC")),
+        "handler panicked: boom".to_owned()
+      ),
+      (
+        "PanicError".to_owned(),
+        500,
+        Some(json!("PANIC")),
+        "handler panicked: boom 7".to_owned()
+      ),
+      (
+        "PanicError".to_owned(),
+        500,
+        Some(json!("INTERNAL_PANIC")),
+        "handler panicked: boom".to_owned()
+      ),
+      (
+        "PanicError".to_owned(),
+        500,
+        Some(json!("PANIC")),
+        "handler panicked: boom".to_owned()
+      ),
+    ]
+  );
+  assert_eq!(
+    CORRELATION_IDS.lock().unwrap().last().map(String::as_str),
+    Some("abc")
+  );
+}
@@ -0,0 +1,97 @@
+use std::sync::Mutex;
+
+use actix_web::{test, web, App};
+use actix_web_thiserror::{ErrorContext, RequestId, ResponseError, ResponseTransform};
+use thiserror::Error;
+
+static LOGS: Mutex<Vec<String>> = Mutex::new(vec![]);
+
+struct Logger;
+
+impl log::Log for Logger {
+  fn enabled(&self, _: &log::Metadata) -> bool {
+    true
+  }
+
+  fn log(&self, record: &log::Record) {
+    LOGS.lock().unwrap().push(record.args().to_string());
+  }
+
+  fn flush(&self) {}
+}
+
+struct EchoTransform;
+
+impl ResponseTransform for EchoTransform {
+  fn transform_with_context(&self, context: &ErrorContext) -> actix_web::HttpResponse {
+    actix_web::HttpResponse::build(context.status_code)
+      .insert_header((actix_web_thiserror::REQUEST_ID_HEADER, context.correlation_id.to_owned()))
+      .finish()
+  }
+}
+
+#[derive(Debug, Error, ResponseError)]
+enum UploadError {
+  #[response(status = 413)]
+  #[error("file too large")]
+  TooLarge,
+}
+
+async fn upload() -> Result<&'static str, UploadError> {
+  Err(UploadError::TooLarge)
+}
+
+fn main() {
+  log::set_logger(&Logger).unwrap();
+  log::set_max_level(log::LevelFilter::Error);
+  actix_web_thiserror::set_global_transform(EchoTransform);
+
+  actix_web::rt::System::new().block_on(async {
+    let app = test::init_service(
+      App::new()
+        .wrap(RequestId)
+        .route("/upload", web::post().to(upload)),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+      .uri("/upload")
+      .insert_header(("X-Request-Id", "req-42"))
+      .to_request();
+    let res = test::call_service(&app, req).await;
+
+    assert_eq!(res.status(), 413);
+    assert_eq!(res.headers().get("X-Request-Id").unwrap(), "req-42");
+    assert_eq!(
+      LOGS.lock().unwrap().pop().unwrap(),
+      "Response error [req-42]: file too large\n\tUploadError(TooLarge)"
+    );
+
+    let req = test::TestRequest::post()
+      .uri("/upload")
+      .insert_header(("X-Request-Id", "bad id\twith whitespace"))
+      .to_request();
+    let res = test::call_service(&app, req).await;
+    let correlation_id = res
+      .headers()
+      .get("X-Request-Id")
+      .unwrap()
+      .to_str()
+      .unwrap()
+      .to_owned();
+
+    assert_eq!(correlation_id.len(), 32);
+    assert!(LOGS
+      .lock()
+      .unwrap()
+      .pop()
+      .unwrap()
+      .starts_with(&format!("Response error [{}]: ", correlation_id)));
+  });
+
+  // Outside of the middleware, every error gets its own id.
+  assert_ne!(
+    actix_web_thiserror::correlation_id(),
+    actix_web_thiserror::correlation_id()
+  );
+}
@@ -0,0 +1,85 @@
+use std::sync::Mutex;
+
+use actix_web::ResponseError as _;
+use actix_web_thiserror::{set_exposure, ErrorContext, Exposure, ResponseError, ResponseTransform};
+use thiserror::Error;
+
+static MESSAGES: Mutex<Vec<Option<String>>> = Mutex::new(vec![]);
+
+struct MessageTransform;
+
+impl ResponseTransform for MessageTransform {
+  fn transform_with_context(&self, context: &ErrorContext) -> actix_web::HttpResponse {
+    MESSAGES.lock().unwrap().push(context.message());
+
+    actix_web::HttpResponse::build(context.status_code).finish()
+  }
+}
+
+#[derive(Debug, Error)]
+#[error("connection refused")]
+struct ConnectionError;
+
+#[derive(Debug, Error, ResponseError)]
+enum Inner {
+  #[response(expose)]
+  #[error("inner not found")]
+  NotFound,
+}
+
+#[derive(Debug, Error, ResponseError)]
+enum DatabaseError {
+  #[error("query failed: SELECT * FROM users")]
+  Query(#[source] ConnectionError),
+  #[response(public = "the user is invalid")]
+  #[error("invalid user {0}")]
+  InvalidUser(u32),
+  #[response(expose)]
+  #[error("user {0} not found")]
+  NotFound(u32),
+  #[response(forward)]
+  #[error(transparent)]
+  Inner(Inner),
+}
+
+fn messages() -> Vec<Option<String>> {
+  let errors = [
+    DatabaseError::Query(ConnectionError),
+    DatabaseError::InvalidUser(1),
+    DatabaseError::NotFound(2),
+    DatabaseError::Inner(Inner::NotFound),
+  ];
+
+  for err in &errors {
+    let _ = err.error_response();
+  }
+
+  std::mem::take(&mut *MESSAGES.lock().unwrap())
+}
+
+fn main() {
+  actix_web_thiserror::set_global_transform(MessageTransform);
+
+  assert_eq!(actix_web_thiserror::exposure(), Exposure::Production);
+  assert_eq!(
+    messages(),
+    vec![
+      None,
+      Some("the user is invalid".to_owned()),
+      Some("user 2 not found".to_owned()),
+      Some("inner not found".to_owned()),
+    ]
+  );
+
+  set_exposure(Exposure::Development);
+
+  assert_eq!(
+    messages(),
+    vec![
+      Some("query failed: SELECT * FROM users: connection refused".to_owned()),
+      Some("invalid user 1".to_owned()),
+      Some("user 2 not found".to_owned()),
+      Some("inner not found".to_owned()),
+    ]
+  );
+}
@@ -0,0 +1,136 @@
+use std::sync::Mutex;
+
+use actix_web::{test, web, App};
+use actix_web_thiserror::{ErrorContext, ResponseTransform};
+use serde::Deserialize;
+use serde_json::json;
+
+type Recorded = (String, String, u16, Option<serde_json::Value>);
+
+static RESPONSES: Mutex<Vec<Recorded>> = Mutex::new(vec![]);
+
+struct RecordingTransform;
+
+impl ResponseTransform for RecordingTransform {
+  fn transform_with_context(&self, context: &ErrorContext) -> actix_web::HttpResponse {
+    RESPONSES.lock().unwrap().push((
+      context.name.to_owned(),
+      context.variant.to_owned(),
+      context.status_code.as_u16(),
+      context.reason.to_owned(),
+    ));
+
+    actix_web::HttpResponse::build(context.status_code).finish()
+  }
+}
+
+#[derive(Deserialize)]
+struct User {
+  #[allow(dead_code)]
+  name: String,
+}
+
+async fn json(_: web::Json<User>) -> &'static str {
+  "ok"
+}
+
+async fn query(_: web::Query<User>) -> &'static str {
+  "ok"
+}
+
+async fn path(_: web::Path<u32>) -> &'static str {
+  "ok"
+}
+
+async fn form(_: web::Form<User>) -> &'static str {
+  "ok"
+}
+
+fn main() {
+  actix_web_thiserror::set_global_transform(RecordingTransform);
+
+  let statuses = actix_web::rt::System::new().block_on(async {
+    let app = test::init_service(
+      App::new()
+        .configure(actix_web_thiserror::extractors::configure)
+        .route("/json", web::post().to(json))
+        .route("/query", web::get().to(query))
+        .route("/path/{id}", web::get().to(path))
+        .route("/form", web::post().to(form)),
+    )
+    .await;
+
+    let requests = [
+      test::TestRequest::post()
+        .uri("/json")
+        .insert_header(("content-type", "application/json"))
+        .set_payload("{\"name\":")
+        .to_request(),
+      test::TestRequest::post()
+        .uri("/json")
+        .insert_header(("content-type", "text/plain"))
+        .set_payload("{}")
+        .to_request(),
+      test::TestRequest::get().uri("/query?id=1").to_request(),
+      test::TestRequest::get().uri("/path/abc").to_request(),
+      test::TestRequest::post()
+        .uri("/form")
+        .insert_header(("content-type", "application/json"))
+        .set_payload("name=a")
+        .to_request(),
+    ];
+
+    let mut statuses = vec![];
+
+    for req in requests {
+      statuses.push(test::call_service(&app, req).await.status().as_u16());
+    }
+
+    statuses
+  });
+
+  let responses = std::mem::take(&mut *RESPONSES.lock().unwrap());
+
+  assert_eq!(
+    responses,
+    vec![
+      (
+        "ExtractorError".to_owned(),
+        "Json".to_owned(),
+        400,
+        Some(json!("INVALID_JSON"))
+      ),
+      (
+        "ExtractorError".to_owned(),
+        "Json".to_owned(),
+        415,
+        Some(json!("UNSUPPORTED_MEDIA_TYPE"))
+      ),
+      (
+        "ExtractorError".to_owned(),
+        "Query".to_owned(),
+        400,
+        Some(json!("INVALID_QUERY"))
+      ),
+      (
+        "ExtractorError".to_owned(),
+        "Path".to_owned(),
+        400,
+        Some(json!("INVALID_PATH"))
+      ),
+      (
+        "ExtractorError".to_owned(),
+        "Form".to_owned(),
+        415,
+        Some(json!("UNSUPPORTED_MEDIA_TYPE"))
+      ),
+    ]
+  );
+  assert_eq!(
+    statuses,
+    responses
+      .iter()
+      .map(|(_, _, status, _)| *status)
+      .collect::<Vec<_>>()
+  );
+}
@@ -0,0 +1,111 @@
+use actix_web::body::MessageBody as _;
+use actix_web::http::StatusCode;
+use actix_web::ResponseError as _;
+use actix_web_thiserror::{
+  with_transform, ErrorResponseParts, FromErrorResponse, JsonTransform, LegacyResultTransform,
+  ResponseError,
+};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+struct Limit {
+  max: usize,
+}
+
+#[derive(Debug, Error, FromErrorResponse, PartialEq, ResponseError)]
+enum StorageError {
+  #[response(status = 503, reason = "STORAGE_UNAVAILABLE")]
+  #[error("storage unavailable")]
+  Unavailable,
+}
+
+#[derive(Debug, Error, FromErrorResponse, PartialEq, ResponseError)]
+#[response(reason_from_variant = "SCREAMING_SNAKE_CASE")]
+enum UploadError {
+  #[response(status = 400)]
+  #[error("invalid image format")]
+  InvalidImageFormat,
+  #[response(status = 413, details = "{0}")]
+  #[error("file too large")]
+  TooLarge(Limit),
+  // Cannot be reconstructed, as the path is not part of the response.
+  #[response(status = 404)]
+  #[error("file {0} not found")]
+  NotFound(String),
+  #[response(forward)]
+  #[error(transparent)]
+  Storage(StorageError),
+}
+
+#[derive(Debug, Error, FromErrorResponse, PartialEq, ResponseError)]
+#[response(auto_forward)]
+enum ApiError {
+  #[error(transparent)]
+  Upload(#[from] UploadError),
+  // Sources that do not implement `FromErrorResponse` are not read through.
+  #[error("formatting failed")]
+  Fmt(#[from] std::fmt::Error),
+}
+
+fn round_trip(err: UploadError) -> Option<UploadError> {
+  let response = err.error_response();
+  let status = response.status();
+  let body = response.into_body().try_into_bytes().unwrap();
+
+  UploadError::from_error_response(status, &body)
+}
+
+fn main() {
+  with_transform(JsonTransform, || {
+    assert_eq!(
+      round_trip(UploadError::InvalidImageFormat),
+      Some(UploadError::InvalidImageFormat)
+    );
+    assert_eq!(
+      round_trip(UploadError::TooLarge(Limit { max: 10 })),
+      Some(UploadError::TooLarge(Limit { max: 10 }))
+    );
+    assert_eq!(round_trip(UploadError::NotFound("a.png".to_owned())), None);
+    assert_eq!(
+      round_trip(UploadError::Storage(StorageError::Unavailable)),
+      Some(UploadError::Storage(StorageError::Unavailable))
+    );
+  });
+
+  // The status code has to match the one declared by the variant.
+  assert_eq!(
+    UploadError::from_error_response(
+      StatusCode::INTERNAL_SERVER_ERROR,
+      br#"{"result": 0, "reason": "INVALID_IMAGE_FORMAT"}"#,
+    ),
+    None
+  );
+  assert_eq!(
+    UploadError::from_error_response(StatusCode::BAD_REQUEST, b"not json"),
+    None
+  );
+  assert_eq!(
+    ApiError::from_error_response(
+      StatusCode::BAD_REQUEST,
+      br#"{"result": 0, "reason": "INVALID_IMAGE_FORMAT"}"#,
+    ),
+    Some(ApiError::Upload(UploadError::InvalidImageFormat))
+  );
+
+  let transform = LegacyResultTransform::new()
+    .reason_key("code")
+    .details_key("data");
+  let parts = transform
+    .parts(StatusCode::PAYLOAD_TOO_LARGE, br#"{"result": 0, "code": "TOO_LARGE", "data": {"max": 5}}"#)
+    .unwrap();
+
+  assert_eq!(
+    UploadError::from_parts(&parts),
+    Some(UploadError::TooLarge(Limit { max: 5 }))
+  );
+  assert_eq!(
+    UploadError::from_parts(&ErrorResponseParts::new(StatusCode::BAD_REQUEST)),
+    None
+  );
+}
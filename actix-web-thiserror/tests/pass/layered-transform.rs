@@ -0,0 +1,94 @@
+use actix_web::body::MessageBody as _;
+use actix_web::http::header::HeaderValue;
+use actix_web::http::StatusCode;
+use actix_web::HttpResponse;
+use actix_web::ResponseError as _;
+use actix_web_thiserror::{ErrorContext, ResponseError, ResponseTransform, ResponseTransformExt as _};
+use serde_json::json;
+use thiserror::Error;
+
+struct ProblemTransform;
+
+impl ResponseTransform for ProblemTransform {
+  fn transform_with_context(&self, context: &ErrorContext) -> HttpResponse {
+    HttpResponse::build(context.status_code).json(json!({
+      "title": context.reason,
+      "detail": context.details,
+    }))
+  }
+
+  fn default_error_status_code(&self) -> StatusCode {
+    StatusCode::SERVICE_UNAVAILABLE
+  }
+}
+
+#[derive(Debug, Error, ResponseError)]
+enum UploadError {
+  #[response(status = 413, reason = "TOO_LARGE", details = "{0}")]
+  #[error("file too large")]
+  TooLarge(usize),
+  #[response(reason = "STORAGE", details = "{0}")]
+  #[error("storage unavailable")]
+  Storage(String),
+}
+
+fn render(err: UploadError) -> (StatusCode, Option<HeaderValue>, serde_json::Value) {
+  let response = err.error_response();
+  let status = response.status();
+  let header = response.headers().get("x-layer").cloned();
+  let body = response.into_body().try_into_bytes().unwrap();
+
+  (status, header, serde_json::from_slice(&body).unwrap())
+}
+
+fn main() {
+  actix_web_thiserror::set_global_transform(
+    ProblemTransform
+      .map_context(|context| {
+        if context.status_code.is_server_error() {
+          context.details = None;
+        }
+      })
+      .then(|context: &ErrorContext, mut response: HttpResponse| {
+        response.headers_mut().insert(
+          "x-layer".parse().unwrap(),
+          HeaderValue::from_str(context.variant).unwrap(),
+        );
+
+        response
+      })
+      .map_response(|mut response| {
+        response
+          .headers_mut()
+          .insert("cache-control".parse().unwrap(), HeaderValue::from_static("no-store"));
+
+        response
+      }),
+  );
+
+  assert_eq!(
+    render(UploadError::TooLarge(10)),
+    (
+      StatusCode::PAYLOAD_TOO_LARGE,
+      Some(HeaderValue::from_static("TooLarge")),
+      json!({ "title": "TOO_LARGE", "detail": 10 }),
+    )
+  );
+  // The default status code is delegated to the innermost transform.
+  assert_eq!(
+    render(UploadError::Storage("s3".to_owned())),
+    (
+      StatusCode::SERVICE_UNAVAILABLE,
+      Some(HeaderValue::from_static("Storage")),
+      json!({ "title": "STORAGE", "detail": null }),
+    )
+  );
+  assert_eq!(
+    UploadError::Storage("s3".to_owned())
+      .error_response()
+      .headers()
+      .get("cache-control")
+      .unwrap(),
+    "no-store"
+  );
+}
@@ -0,0 +1,64 @@
+use actix_web::body::MessageBody as _;
+use actix_web::ResponseError as _;
+use actix_web_thiserror::{with_transform, LegacyResultTransform, ResponseError};
+use serde_json::json;
+use thiserror::Error;
+
+#[derive(Debug, Error, ResponseError)]
+enum Base64ImageError {
+  #[response(reason = "INVALID_IMAGE_FORMAT")]
+  #[error("invalid image format")]
+  InvalidImageFormat,
+  #[response(status = 413, reason = "TOO_LARGE", type = "image", details = "{0}", expose)]
+  #[error("image too large")]
+  TooLarge(usize),
+  #[error("storage unavailable")]
+  Unavailable,
+}
+
+fn body(err: Base64ImageError) -> serde_json::Value {
+  let body = err.error_response().into_body().try_into_bytes().unwrap();
+
+  serde_json::from_slice(&body).unwrap()
+}
+
+fn main() {
+  with_transform(LegacyResultTransform::new(), || {
+    assert_eq!(
+      body(Base64ImageError::InvalidImageFormat),
+      json!({ "result": 0, "reason": "INVALID_IMAGE_FORMAT" })
+    );
+    assert_eq!(
+      body(Base64ImageError::TooLarge(10)),
+      json!({ "result": 0, "reason": "TOO_LARGE" })
+    );
+    assert_eq!(
+      body(Base64ImageError::Unavailable),
+      json!({ "result": 0, "reason": null })
+    );
+  });
+
+  let transform = LegacyResultTransform::new()
+    .result("success", false)
+    .reason_key("code")
+    .type_key("kind")
+    .details_key("data")
+    .message_key("message");
+
+  with_transform(transform, || {
+    assert_eq!(
+      body(Base64ImageError::InvalidImageFormat),
+      json!({ "success": false, "code": "INVALID_IMAGE_FORMAT" })
+    );
+    assert_eq!(
+      body(Base64ImageError::TooLarge(10)),
+      json!({
+        "success": false,
+        "code": "TOO_LARGE",
+        "kind": "image",
+        "data": 10,
+        "message": "image too large",
+      })
+    );
+  });
+}
@@ -0,0 +1,79 @@
+use actix_web::http::StatusCode;
+use actix_web::ResponseError as _;
+use actix_web_thiserror::{MetaValue, ResponseError, ResponseMeta, ThiserrorResponse};
+use serde_json::json;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+#[error(transparent)]
+struct DatabaseError(std::io::Error);
+
+impl ThiserrorResponse for DatabaseError {
+  fn response_meta(&self) -> ResponseMeta {
+    match self.0.kind() {
+      std::io::ErrorKind::NotFound => ResponseMeta::new()
+        .status_code(StatusCode::NOT_FOUND)
+        .reason("NOT_FOUND")
+        .details(json!({ "table": "users" })),
+      _ => ResponseMeta::new().reason("DATABASE_ERROR"),
+    }
+  }
+}
+
+#[derive(Debug, Error, ResponseError)]
+enum UserError {
+  #[response(forward)]
+  #[error(transparent)]
+  Database(DatabaseError),
+  #[response(status = 409, reason = "CONFLICT")]
+  #[error("user exists")]
+  Exists,
+}
+
+#[derive(Debug, Error, ResponseError)]
+enum ApiError {
+  #[response(forward)]
+  #[error(transparent)]
+  User(UserError),
+}
+
+fn not_found() -> DatabaseError {
+  DatabaseError(std::io::Error::from(std::io::ErrorKind::NotFound))
+}
+
+fn main() {
+  let meta = UserError::Database(not_found()).response_meta();
+
+  assert_eq!(meta.status_code, Some(StatusCode::NOT_FOUND));
+  assert_eq!(meta.reason, MetaValue::Set(json!("NOT_FOUND")));
+  assert_eq!(meta.details, MetaValue::Set(json!({ "table": "users" })));
+  assert_eq!(meta._type, MetaValue::Unset);
+
+  // Forwarding goes through any number of levels.
+  let err = ApiError::User(UserError::Database(not_found()));
+
+  assert_eq!(err.response_meta(), meta);
+  assert_eq!(err.status_code(), StatusCode::NOT_FOUND);
+
+  let err = ApiError::User(UserError::Database(DatabaseError(std::io::Error::other(
+    "connection reset",
+  ))));
+
+  assert_eq!(err.status_code(), StatusCode::INTERNAL_SERVER_ERROR);
+  assert_eq!(
+    err.response_meta().reason,
+    MetaValue::Set(json!("DATABASE_ERROR"))
+  );
+
+  assert_eq!(
+    ApiError::User(UserError::Exists).status_code(),
+    StatusCode::CONFLICT
+  );
+
+  // Explicitly omitted values are not filled in by forwarding.
+  let mut omitted = ResponseMeta::new();
+  omitted.details = MetaValue::Omitted;
+
+  assert_eq!(omitted.or(meta.to_owned()).details, MetaValue::Omitted);
+  assert_eq!(ResponseMeta::new().or(meta.to_owned()).details, meta.details);
+}
@@ -0,0 +1,177 @@
+use std::sync::Mutex;
+
+use actix_web::dev::Service as _;
+use actix_web::{test, web, App, HttpResponse};
+use actix_web_thiserror::{ErrorContext, ErrorNormalizer, ResponseError, ResponseTransform};
+use serde_json::json;
+use thiserror::Error;
+
+type Recorded = (String, u16, Option<serde_json::Value>, String);
+
+static RESPONSES: Mutex<Vec<Recorded>> = Mutex::new(vec![]);
+
+struct RecordingTransform;
+
+impl ResponseTransform for RecordingTransform {
+  fn transform_with_context(&self, context: &ErrorContext) -> actix_web::HttpResponse {
+    RESPONSES.lock().unwrap().push((
+      context.name.to_owned(),
+      context.status_code.as_u16(),
+      context.reason.to_owned(),
+      context.err.to_string(),
+    ));
+
+    actix_web::HttpResponse::build(context.status_code).finish()
+  }
+}
+
+#[derive(Debug, Error, ResponseError)]
+pub enum UserError {
+  #[response(status = 404, reason = "USER_NOT_FOUND")]
+  #[error("user not found")]
+  NotFound,
+}
+
+async fn user() -> Result<HttpResponse, UserError> {
+  Err(UserError::NotFound)
+}
+
+async fn forbidden() -> Result<HttpResponse, actix_web::Error> {
+  Err(actix_web::error::ErrorForbidden("not allowed"))
+}
+
+async fn custom() -> HttpResponse {
+  HttpResponse::BadRequest().body("custom")
+}
+
+async fn unauthorized() -> HttpResponse {
+  HttpResponse::Unauthorized()
+    .insert_header(("www-authenticate", "Bearer"))
+    .finish()
+}
+
+async fn create() -> &'static str {
+  "ok"
+}
+
+fn main() {
+  actix_web_thiserror::set_global_transform(RecordingTransform);
+
+  let statuses = actix_web::rt::System::new().block_on(async {
+    let app = test::init_service(
+      App::new()
+        .wrap(ErrorNormalizer)
+        .route("/user", web::get().to(user))
+        .route("/forbidden", web::get().to(forbidden))
+        .route("/custom", web::get().to(custom))
+        .route("/unauthorized", web::get().to(unauthorized))
+        .service(web::resource("/create").route(web::post().to(create))),
+    )
+    .await;
+
+    let mut statuses = vec![];
+
+    for uri in ["/user", "/forbidden", "/custom", "/create", "/missing"] {
+      let req = test::TestRequest::get().uri(uri).to_request();
+      statuses.push(test::call_service(&app, req).await.status().as_u16());
+    }
+
+    let req = test::TestRequest::get().uri("/unauthorized").to_request();
+    let res = test::call_service(&app, req).await;
+    statuses.push(res.status().as_u16());
+    assert_eq!(res.headers().get("www-authenticate").unwrap(), "Bearer");
+
+    // Errors returned by inner middleware instead of a response.
+    let guarded = test::init_service(
+      App::new()
+        .wrap_fn(|req, srv| {
+          let path = req.path().to_owned();
+          let fut = srv.call(req);
+
+          async move {
+            match path.as_str() {
+              "/denied" => Err(actix_web::error::ErrorUnauthorized("missing token")),
+              "/gone" => Err(UserError::NotFound.into()),
+              _ => fut.await,
+            }
+          }
+        })
+        .wrap(ErrorNormalizer)
+        .route("/create", web::post().to(create)),
+    )
+    .await;
+
+    for uri in ["/denied", "/gone"] {
+      let req = test::TestRequest::get().uri(uri).to_request();
+      let err = test::try_call_service(&guarded, req).await.unwrap_err();
+      statuses.push(err.error_response().status().as_u16());
+    }
+
+    let app = test::init_service(
+      App::new()
+        .route("/create", web::post().to(create))
+        .default_service(actix_web_thiserror::default_service()),
+    )
+    .await;
+
+    let req = test::TestRequest::get().uri("/missing").to_request();
+    statuses.push(test::call_service(&app, req).await.status().as_u16());
+
+    statuses
+  });
+
+  assert_eq!(statuses, vec![404, 403, 400, 405, 404, 401, 401, 404, 404]);
+  assert_eq!(
+    std::mem::take(&mut *RESPONSES.lock().unwrap()),
+    vec![
+      (
+        "UserError".to_owned(),
+        404,
+        Some(json!("USER_NOT_FOUND")),
+        "user not found".to_owned()
+      ),
+      (
+        "NormalizedError".to_owned(),
+        403,
+        Some(json!("FORBIDDEN")),
+        "not allowed".to_owned()
+      ),
+      (
+        "NormalizedError".to_owned(),
+        405,
+        Some(json!("METHOD_NOT_ALLOWED")),
+        "Method Not Allowed".to_owned()
+      ),
+      (
+        "NormalizedError".to_owned(),
+        404,
+        Some(json!("NOT_FOUND")),
+        "Not Found".to_owned()
+      ),
+      (
+        "NormalizedError".to_owned(),
+        401,
+        Some(json!("UNAUTHORIZED")),
+        "Unauthorized".to_owned()
+      ),
+      (
+        "NormalizedError".to_owned(),
+        401,
+        Some(json!("UNAUTHORIZED")),
+        "missing token".to_owned()
+      ),
+      (
+        "UserError".to_owned(),
+        404,
+        Some(json!("USER_NOT_FOUND")),
+        "user not found".to_owned()
+      ),
+      (
+        "NormalizedError".to_owned(),
+        404,
+        Some(json!("NOT_FOUND")),
+        "Not Found".to_owned()
+      ),
+    ]
+  );
+}
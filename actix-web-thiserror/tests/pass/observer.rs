@@ -0,0 +1,97 @@
+use std::sync::Mutex;
+
+use actix_web::ResponseError as _;
+use actix_web_thiserror::{ErrorContext, ErrorObserver, ResponseError};
+use thiserror::Error;
+
+static EVENTS: Mutex<Vec<(&'static str, String, String, u16)>> = Mutex::new(vec![]);
+
+struct TestSink(&'static str);
+
+impl ErrorObserver for TestSink {
+  fn observe(&self, context: &ErrorContext) {
+    EVENTS.lock().unwrap().push((
+      self.0,
+      context.name.to_owned(),
+      context.variant.to_owned(),
+      context.status_code.as_u16(),
+    ));
+  }
+}
+
+struct BrokenSink;
+
+impl ErrorObserver for BrokenSink {
+  fn observe(&self, _: &ErrorContext) {
+    panic!("sink unavailable");
+  }
+}
+
+struct SourceSink;
+
+impl ErrorObserver for SourceSink {
+  fn observe(&self, context: &ErrorContext) {
+    if let Some(source) = context.err.source() {
+      EVENTS
+        .lock()
+        .unwrap()
+        .push(("source", source.to_string(), String::new(), 0));
+    }
+  }
+}
+
+#[derive(Debug, Error, ResponseError)]
+enum Inner {
+  #[response(status = 404)]
+  #[error("inner not found")]
+  NotFound,
+}
+
+#[derive(Debug, Error)]
+#[error("connection refused")]
+struct ConnectionError;
+
+#[derive(Debug, Error, ResponseError)]
+enum UploadError {
+  #[response(status = 413)]
+  #[error("file too large")]
+  TooLarge { size: usize },
+  #[error("storage unavailable")]
+  Storage(#[source] ConnectionError),
+  #[response(forward)]
+  #[error(transparent)]
+  Inner(Inner),
+}
+
+fn main() {
+  std::panic::set_hook(Box::new(|_| {}));
+
+  actix_web_thiserror::register_error_observer(TestSink("first"));
+  actix_web_thiserror::register_error_observer(BrokenSink);
+  actix_web_thiserror::register_error_observer(TestSink("second"));
+  actix_web_thiserror::register_error_observer(SourceSink);
+
+  let errors = [
+    UploadError::TooLarge { size: 1 },
+    UploadError::Storage(ConnectionError),
+    UploadError::Inner(Inner::NotFound),
+  ];
+  let statuses = errors
+    .iter()
+    .map(|err| err.error_response().status().as_u16())
+    .collect::<Vec<_>>();
+
+  assert_eq!(statuses, vec![413, 500, 404]);
+  assert_eq!(
+    std::mem::take(&mut *EVENTS.lock().unwrap()),
+    vec![
+      ("first", "UploadError".to_owned(), "TooLarge".to_owned(), 413),
+      ("second", "UploadError".to_owned(), "TooLarge".to_owned(), 413),
+      ("first", "UploadError".to_owned(), "Storage".to_owned(), 500),
+      ("second", "UploadError".to_owned(), "Storage".to_owned(), 500),
+      ("source", "connection refused".to_owned(), String::new(), 0),
+      ("first", "UploadError".to_owned(), "Inner".to_owned(), 404),
+      ("second", "UploadError".to_owned(), "Inner".to_owned(), 404),
+    ]
+  );
+}
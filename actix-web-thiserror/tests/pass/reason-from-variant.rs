@@ -0,0 +1,56 @@
+use actix_web_thiserror::{MetaValue, ResponseError, ThiserrorResponse};
+use serde_json::json;
+use thiserror::Error;
+
+#[derive(Debug, Error, ResponseError)]
+#[response(reason_from_variant = "SCREAMING_SNAKE_CASE")]
+enum ImageError {
+  #[error("invalid image format")]
+  InvalidImageFormat,
+  #[response(reason = "BAD_STRING")]
+  #[error("invalid string")]
+  InvalidString,
+}
+
+#[derive(Debug, Error, ResponseError)]
+#[response(reason_from_variant = "kebab-case", reason_prefix = "img-")]
+enum PrefixedError {
+  #[error("image too large")]
+  ImageTooLarge,
+  #[response(forward)]
+  #[error(transparent)]
+  Image(ImageError),
+}
+
+#[derive(Debug, Error, ResponseError)]
+#[response(reason_from_variant = "camelCase")]
+enum CamelError {
+  #[error("http timeout")]
+  HttpTimeout,
+}
+
+fn main() {
+  assert_eq!(
+    ImageError::InvalidImageFormat.response_meta().reason,
+    MetaValue::Set(json!("INVALID_IMAGE_FORMAT"))
+  );
+  assert_eq!(
+    ImageError::InvalidString.response_meta().reason,
+    MetaValue::Set(json!("BAD_STRING"))
+  );
+  assert_eq!(
+    PrefixedError::ImageTooLarge.response_meta().reason,
+    MetaValue::Set(json!("img-image-too-large"))
+  );
+  // Forwarded variants keep the reason of the error they forward to.
+  assert_eq!(
+    PrefixedError::Image(ImageError::InvalidImageFormat)
+      .response_meta()
+      .reason,
+    MetaValue::Set(json!("INVALID_IMAGE_FORMAT"))
+  );
+  assert_eq!(
+    CamelError::HttpTimeout.response_meta().reason,
+    MetaValue::Set(json!("httpTimeout"))
+  );
+}
@@ -0,0 +1,69 @@
+use std::sync::{Arc, Barrier};
+
+use actix_web::body::MessageBody as _;
+use actix_web::ResponseError as _;
+use actix_web_thiserror::{with_transform, ErrorContext, ResponseError, ResponseTransform, TransformGuard};
+use thiserror::Error;
+
+struct BodyTransform(&'static str);
+
+impl ResponseTransform for BodyTransform {
+  fn transform_with_context(&self, context: &ErrorContext) -> actix_web::HttpResponse {
+    actix_web::HttpResponse::build(context.status_code).body(self.0)
+  }
+}
+
+#[derive(Debug, Error, ResponseError)]
+enum UploadError {
+  #[response(status = 413)]
+  #[error("file too large")]
+  TooLarge,
+}
+
+fn body() -> String {
+  let body = UploadError::TooLarge
+    .error_response()
+    .into_body()
+    .try_into_bytes()
+    .unwrap();
+
+  String::from_utf8(body.to_vec()).unwrap()
+}
+
+fn main() {
+  actix_web_thiserror::set_global_transform(BodyTransform("global"));
+
+  let barrier = Arc::new(Barrier::new(2));
+  let threads = ["first", "second"].map(|name| {
+    let barrier = barrier.to_owned();
+
+    std::thread::spawn(move || {
+      with_transform(BodyTransform(name), || {
+        barrier.wait();
+
+        let body = body();
+        barrier.wait();
+
+        body
+      })
+    })
+  });
+  let bodies = threads.map(|thread| thread.join().unwrap());
+
+  assert_eq!(bodies, ["first", "second"]);
+  assert_eq!(body(), "global");
+
+  {
+    let _outer = TransformGuard::new(BodyTransform("outer"));
+    assert_eq!(body(), "outer");
+
+    {
+      let _inner = TransformGuard::new(BodyTransform("inner"));
+      assert_eq!(body(), "inner");
+    }
+
+    assert_eq!(body(), "outer");
+  }
+
+  assert_eq!(body(), "global");
+}
@@ -0,0 +1,161 @@
+use std::sync::Mutex;
+
+use actix_web::ResponseError as _;
+use actix_web_thiserror::{set_exposure, ErrorContext, Exposure, ResponseError, ResponseTransform};
+use thiserror::Error;
+
+static LOGS: Mutex<Vec<String>> = Mutex::new(vec![]);
+static CONTEXTS: Mutex<Vec<(Option<serde_json::Value>, Option<String>)>> = Mutex::new(vec![]);
+
+struct Logger;
+
+impl log::Log for Logger {
+  fn enabled(&self, _: &log::Metadata) -> bool {
+    true
+  }
+
+  fn log(&self, record: &log::Record) {
+    let line = record.args().to_string();
+    let (_, line) = line.split_once("]: ").unwrap();
+
+    LOGS.lock().unwrap().push(line.to_owned());
+  }
+
+  fn flush(&self) {}
+}
+
+struct ContextTransform;
+
+impl ResponseTransform for ContextTransform {
+  fn transform_with_context(&self, context: &ErrorContext) -> actix_web::HttpResponse {
+    CONTEXTS
+      .lock()
+      .unwrap()
+      .push((context.details.to_owned(), context.message()));
+
+    actix_web::HttpResponse::build(context.status_code).finish()
+  }
+}
+
+#[derive(Debug, Error, ResponseError)]
+enum AuthError {
+  #[response(expose, details = "{0}")]
+  #[error("invalid token {0}")]
+  InvalidToken(#[response(sensitive)] String),
+  #[response(expose)]
+  #[error("wrong password for {user}")]
+  WrongPassword {
+    user: String,
+    #[response(sensitive)]
+    password: String,
+  },
+  #[response(expose)]
+  #[error("locked out {user} with {}", password.len())]
+  LockedOut {
+    user: String,
+    #[response(sensitive)]
+    password: String,
+  },
+  #[error("internal")]
+  Internal,
+}
+
+// Sensitive fields stay masked when the error is wrapped.
+#[derive(Debug, Error, ResponseError)]
+enum ApiError {
+  #[response(forward)]
+  #[error(transparent)]
+  Auth(AuthError),
+  #[response(forward)]
+  #[error("login failed: {0}")]
+  Login(#[source] AuthError),
+}
+
+#[derive(Debug, Error, ResponseError)]
+#[response(auto_forward)]
+enum AutoError {
+  #[error(transparent)]
+  Api(#[from] ApiError),
+  #[error("formatting failed")]
+  Fmt(#[from] std::fmt::Error),
+}
+
+fn main() {
+  log::set_logger(&Logger).unwrap();
+  log::set_max_level(log::LevelFilter::Error);
+  actix_web_thiserror::set_global_transform(ContextTransform);
+
+  let errors = [
+    AuthError::InvalidToken("secret-token".to_owned()),
+    AuthError::WrongPassword {
+      user: "alice".to_owned(),
+      password: "hunter2".to_owned(),
+    },
+    AuthError::LockedOut {
+      user: "bob".to_owned(),
+      password: "hunter2".to_owned(),
+    },
+    AuthError::Internal,
+  ];
+
+  for err in &errors {
+    let _ = err.error_response();
+  }
+
+  assert_eq!(
+    std::mem::take(&mut *LOGS.lock().unwrap()),
+    vec![
+      "invalid token [REDACTED]\n\tAuthError(InvalidToken([REDACTED]))",
+      "wrong password for alice\n\tAuthError(WrongPassword { user: \"alice\", password: [REDACTED] })",
+      "[REDACTED]\n\tAuthError(LockedOut { user: \"bob\", password: [REDACTED] })",
+      "internal\n\tAuthError(Internal)",
+    ]
+  );
+  assert_eq!(
+    std::mem::take(&mut *CONTEXTS.lock().unwrap()),
+    vec![
+      (
+        Some(serde_json::json!("[REDACTED]")),
+        Some("invalid token [REDACTED]".to_owned())
+      ),
+      (None, Some("wrong password for alice".to_owned())),
+      (None, Some("[REDACTED]".to_owned())),
+      (None, None),
+    ]
+  );
+
+  set_exposure(Exposure::Development);
+  let _ = errors[0].error_response();
+
+  assert_eq!(
+    CONTEXTS.lock().unwrap().pop().and_then(|(_, message)| message),
+    Some("invalid token [REDACTED]".to_owned())
+  );
+
+  // The errors themselves are left untouched.
+  assert_eq!(errors[0].to_string(), "invalid token secret-token");
+
+  let wrong_password = || AuthError::WrongPassword {
+    user: "alice".to_owned(),
+    password: "hunter2".to_owned(),
+  };
+
+  LOGS.lock().unwrap().clear();
+  let _ = ApiError::Auth(wrong_password()).error_response();
+  let _ = ApiError::Login(wrong_password()).error_response();
+  let _ = ApiError::Auth(AuthError::Internal).error_response();
+  let _ = AutoError::Api(ApiError::Auth(AuthError::InvalidToken("secret-token".to_owned())))
+    .error_response();
+  let _ = AutoError::Fmt(std::fmt::Error).error_response();
+
+  assert_eq!(
+    std::mem::take(&mut *LOGS.lock().unwrap()),
+    vec![
+      "wrong password for alice\n\tApiError(Auth(WrongPassword { user: \"alice\", password: [REDACTED] }))",
+      "login failed: wrong password for alice\n\tApiError(Login(WrongPassword { user: \"alice\", password: [REDACTED] }))",
+      "internal\n\tApiError(Auth(Internal))",
+      "invalid token [REDACTED]\n\tAutoError(Api(Auth(InvalidToken([REDACTED]))))",
+      "formatting failed\n\tAutoError(Fmt(Error))",
+    ]
+  );
+}
@@ -0,0 +1,135 @@
+use actix_web::body::MessageBody as _;
+use actix_web::http::StatusCode;
+use actix_web::HttpResponse;
+use actix_web_thiserror::{
+  ErrorContext, LegacyResultTransform, ResponseError, ResponseTransform, ThiserrorCatalog,
+};
+use thiserror::Error;
+
+mod transforms {
+  use super::*;
+
+  pub struct TextTransform;
+
+  impl ResponseTransform for TextTransform {
+    fn transform_with_context(&self, context: &ErrorContext) -> HttpResponse {
+      HttpResponse::build(context.status_code).body(format!("text: {}", context.variant))
+    }
+
+    fn default_error_status_code(&self) -> StatusCode {
+      StatusCode::BAD_GATEWAY
+    }
+  }
+
+  pub struct PrefixTransform(pub &'static str);
+
+  impl ResponseTransform for PrefixTransform {
+    fn transform_with_context(&self, context: &ErrorContext) -> HttpResponse {
+      HttpResponse::build(context.status_code).body(format!("{}: {}", self.0, context.variant))
+    }
+  }
+
+  pub const LOUD: PrefixTransform = PrefixTransform("LOUD");
+}
+
+struct GlobalTransform;
+
+impl ResponseTransform for GlobalTransform {
+  fn transform_with_context(&self, context: &ErrorContext) -> HttpResponse {
+    HttpResponse::build(context.status_code).body(format!("global: {}", context.variant))
+  }
+
+  fn default_error_status_code(&self) -> StatusCode {
+    StatusCode::SERVICE_UNAVAILABLE
+  }
+}
+
+#[derive(Debug, Error, ResponseError)]
+#[response(transform = transforms::TextTransform)]
+enum UploadError {
+  #[error("storage unavailable")]
+  Unavailable,
+  #[response(status = 413, transform = transforms::LOUD)]
+  #[error("file too large")]
+  TooLarge,
+  #[response(transform = transforms::PrefixTransform("quiet"))]
+  #[error("file empty")]
+  Empty,
+  #[response(reason = "LEGACY", transform = LegacyResultTransform::new())]
+  #[error("legacy")]
+  Legacy,
+}
+
+#[derive(Debug, Error, ResponseError)]
+enum ImageError {
+  #[error("invalid image")]
+  Invalid,
+  #[response(transform = custom)]
+  #[error("custom image")]
+  Custom,
+}
+
+impl ResponseTransform for ImageError {
+  fn transform_with_context(&self, context: &ErrorContext) -> HttpResponse {
+    HttpResponse::build(context.status_code).body(format!("custom: {}", self))
+  }
+}
+
+fn render(err: &dyn actix_web::ResponseError) -> (StatusCode, String) {
+  let response = err.error_response();
+  let status = response.status();
+  let body = response.into_body().try_into_bytes().unwrap();
+
+  (status, String::from_utf8(body.to_vec()).unwrap())
+}
+
+fn main() {
+  actix_web_thiserror::set_global_transform(GlobalTransform);
+
+  assert_eq!(
+    render(&UploadError::Unavailable),
+    (StatusCode::BAD_GATEWAY, "text: Unavailable".to_owned())
+  );
+  assert_eq!(
+    render(&UploadError::TooLarge),
+    (StatusCode::PAYLOAD_TOO_LARGE, "LOUD: TooLarge".to_owned())
+  );
+  // The default status code comes from the transform of the variant.
+  assert_eq!(
+    render(&UploadError::Empty),
+    (StatusCode::INTERNAL_SERVER_ERROR, "quiet: Empty".to_owned())
+  );
+  assert_eq!(
+    render(&UploadError::Legacy).1,
+    r#"{"reason":"LEGACY","result":0}"#
+  );
+  assert_eq!(
+    render(&ImageError::Invalid),
+    (StatusCode::SERVICE_UNAVAILABLE, "global: Invalid".to_owned())
+  );
+  assert_eq!(
+    render(&ImageError::Custom),
+    (StatusCode::INTERNAL_SERVER_ERROR, "custom: custom image".to_owned())
+  );
+
+  // The catalog reports the default status codes of the same transforms.
+  let statuses = UploadError::error_catalog()
+    .entries
+    .iter()
+    .map(|entry| entry.status_code())
+    .collect::<Vec<_>>();
+
+  assert_eq!(
+    statuses,
+    vec![
+      StatusCode::BAD_GATEWAY,
+      StatusCode::PAYLOAD_TOO_LARGE,
+      StatusCode::INTERNAL_SERVER_ERROR,
+      StatusCode::INTERNAL_SERVER_ERROR,
+    ]
+  );
+  assert_eq!(
+    ImageError::error_catalog().entries[0].status_code(),
+    StatusCode::SERVICE_UNAVAILABLE
+  );
+}
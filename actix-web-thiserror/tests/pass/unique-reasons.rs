@@ -0,0 +1,27 @@
+use actix_web_thiserror::ResponseError;
+use thiserror::Error;
+
+const NOT_FOUND: &str = "NOT_FOUND";
+
+#[derive(Debug, Error, ResponseError)]
+#[response(unique_reasons)]
+enum MacroErrors {
+  #[response(reason = "NOT_FOUND")]
+  #[error("user not found")]
+  UserNotFound,
+  #[response(reason = "IMAGE_NOT_FOUND")]
+  #[error("image not found")]
+  ImageNotFound,
+  /// Only literal reasons are checked.
+  #[response(reason = NOT_FOUND)]
+  #[error("file not found")]
+  FileNotFound,
+}
+
+fn main() {
+  let _ = (
+    MacroErrors::UserNotFound,
+    MacroErrors::ImageNotFound,
+    MacroErrors::FileNotFound,
+  );
+}
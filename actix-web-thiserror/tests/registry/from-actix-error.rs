@@ -0,0 +1,89 @@
+use std::sync::Mutex;
+
+use actix_web::dev::Service as _;
+use actix_web::http::StatusCode;
+use actix_web::{test, web, App};
+use actix_web_thiserror::registry::RegisteredError;
+use actix_web_thiserror::{ApiError, MetaValue, ResponseError};
+use serde_json::json;
+use thiserror::Error;
+
+type Recorded = (&'static str, &'static str, u16, MetaValue<serde_json::Value>);
+
+static ERRORS: Mutex<Vec<Option<Recorded>>> = Mutex::new(vec![]);
+
+#[derive(Debug, Error, ResponseError)]
+enum UserError {
+  #[response(status = 404, reason = "USER_NOT_FOUND")]
+  #[error("user not found")]
+  NotFound,
+  #[response(status = 400, reason = "INVALID_NAME", details = "{0}")]
+  #[error("invalid name")]
+  InvalidName(String),
+}
+
+async fn missing() -> Result<&'static str, UserError> {
+  Err(UserError::NotFound)
+}
+
+async fn rename() -> Result<&'static str, UserError> {
+  Err(UserError::InvalidName("???".to_owned()))
+}
+
+async fn conflict() -> Result<&'static str, ApiError> {
+  Err(ApiError::new(StatusCode::CONFLICT).reason("VERSION_MISMATCH"))
+}
+
+async fn plain() -> Result<&'static str, actix_web::Error> {
+  Err(actix_web::error::ErrorBadRequest("plain"))
+}
+
+fn main() {
+  actix_web::rt::System::new().block_on(async {
+    let app = test::init_service(
+      App::new()
+        .wrap_fn(|req, srv| {
+          let res = srv.call(req);
+
+          async move {
+            let res = res.await?;
+            let registered = res
+              .response()
+              .error()
+              .and_then(RegisteredError::from_actix_error)
+              .map(|registered| {
+                (
+                  registered.name,
+                  registered.variant,
+                  registered.status_code.as_u16(),
+                  registered.meta.details,
+                )
+              });
+
+            ERRORS.lock().unwrap().push(registered);
+
+            Ok(res)
+          }
+        })
+        .route("/missing", web::get().to(missing))
+        .route("/rename", web::get().to(rename))
+        .route("/conflict", web::get().to(conflict))
+        .route("/plain", web::get().to(plain)),
+    )
+    .await;
+
+    for uri in ["/missing", "/rename", "/conflict", "/plain"] {
+      let _ = test::call_service(&app, test::TestRequest::get().uri(uri).to_request()).await;
+    }
+  });
+
+  assert_eq!(
+    std::mem::take(&mut *ERRORS.lock().unwrap()),
+    vec![
+      Some(("UserError", "NotFound", 404, MetaValue::Unset)),
+      Some(("UserError", "InvalidName", 400, MetaValue::Set(json!("???")))),
+      Some(("ApiError", "", 409, MetaValue::Unset)),
+      None,
+    ]
+  );
+}
@@ -0,0 +1,65 @@
+use actix_web::http::StatusCode;
+use actix_web_thiserror::test_util::{render, RecordingTransform};
+use actix_web_thiserror::{assert_response, with_transform, LegacyResultTransform, ResponseError};
+use serde_json::json;
+use thiserror::Error;
+
+#[derive(Debug, Error, ResponseError)]
+pub enum UserError {
+  #[response(status = 404, reason = "USER_NOT_FOUND")]
+  #[error("user not found")]
+  NotFound,
+  #[response(status = 400, reason = "INVALID_NAME", type = "validation", public = "The name is invalid.")]
+  #[error("invalid name {0}")]
+  InvalidName(String),
+  #[error("internal")]
+  Internal,
+}
+
+fn main() {
+  assert_eq!(render(&UserError::NotFound), (StatusCode::NOT_FOUND, json!(null)));
+
+  assert_response!(UserError::NotFound, status = 404, reason = "USER_NOT_FOUND");
+  assert_response!(UserError::Internal, status = StatusCode::INTERNAL_SERVER_ERROR);
+  assert_response!(
+    UserError::InvalidName("a".to_owned()),
+    status = 400,
+    reason = "INVALID_NAME",
+    _type = "validation",
+    public_message = "The name is invalid.",
+    body = json!(null),
+  );
+
+  with_transform(LegacyResultTransform::new(), || {
+    assert_eq!(
+      render(&UserError::NotFound),
+      (
+        StatusCode::NOT_FOUND,
+        json!({ "result": 0, "reason": "USER_NOT_FOUND" })
+      )
+    );
+    assert_response!(
+      UserError::NotFound,
+      reason = "USER_NOT_FOUND",
+      body = json!({ "result": 0, "reason": "USER_NOT_FOUND" }),
+    );
+  });
+
+  let recorder = RecordingTransform::wrapping(LegacyResultTransform::new());
+
+  let (status_code, body) = with_transform(recorder.clone(), || {
+    render(&UserError::InvalidName("a".to_owned()))
+  });
+
+  assert_eq!(status_code, StatusCode::BAD_REQUEST);
+  assert_eq!(body["reason"], json!("INVALID_NAME"));
+
+  let calls = recorder.take();
+  assert_eq!(calls.len(), 1);
+  assert_eq!(calls[0].name, "UserError");
+  assert_eq!(calls[0].variant, "InvalidName");
+  assert_eq!(calls[0].message, "invalid name a");
+  assert_eq!(calls[0].status_code, StatusCode::BAD_REQUEST);
+  assert_eq!(calls[0]._type.as_deref(), Some("validation"));
+  assert!(recorder.calls().is_empty());
+}